@@ -0,0 +1,97 @@
+extern crate criterion;
+extern crate hyper;
+extern crate hyper_router;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use hyper::{Body, Method, Request, Response};
+use hyper_router::*;
+
+const RESOURCES: usize = 100;
+
+fn handle_any(_: Request<Body>) -> Response<Body> {
+    Response::new(Body::from("handled"))
+}
+
+/// Patterns of a REST API with three routes per resource.
+fn patterns() -> Vec<String> {
+    (0..RESOURCES)
+        .flat_map(|resource| {
+            vec![
+                format!("/api/resource{}", resource),
+                format!("/api/resource{}/:id", resource),
+                format!("/api/resource{}/{{id:u64}}/items/:item", resource),
+            ]
+        })
+        .collect()
+}
+
+fn request(path: &str) -> Request<Body> {
+    Request::builder()
+        .method(Method::GET)
+        .uri(path)
+        .body(Body::empty())
+        .unwrap()
+}
+
+fn matching(c: &mut Criterion) {
+    let patterns = patterns();
+    let router = patterns
+        .iter()
+        .fold(RouterBuilder::new(), |builder, pattern| {
+            builder.add(Route::get(pattern).using(handle_any))
+        })
+        .build()
+        .unwrap();
+    let paths: Vec<Path> = patterns.iter().map(|pattern| Path::new(pattern)).collect();
+
+    for path in &["/api/resource0", "/api/resource99/42/items/7", "/missing"] {
+        let request = request(path);
+        c.bench_function(&format!("tree {}", path), |b| {
+            b.iter(|| black_box(router.find_route(&request).is_ok()))
+        });
+        c.bench_function(&format!("linear scan {}", path), |b| {
+            b.iter(|| {
+                black_box(
+                    paths
+                        .iter()
+                        .filter(|candidate| candidate.is_match(path))
+                        .collect::<Vec<_>>(),
+                )
+            })
+        });
+    }
+}
+
+/// Routes that need their regex, matched with a `RegexSet` instead of one by one.
+fn regex_matching(c: &mut Criterion) {
+    let patterns: Vec<String> = (0..RESOURCES)
+        .map(|resource| format!(r"/archive{}/\d{{4}}/(?P<slug>[a-z-]+)\.html", resource))
+        .collect();
+    let router = patterns
+        .iter()
+        .fold(RouterBuilder::new(), |builder, pattern| {
+            builder.add(Route::get(pattern).using(handle_any))
+        })
+        .build()
+        .unwrap();
+    let paths: Vec<Path> = patterns.iter().map(|pattern| Path::new(pattern)).collect();
+
+    let path = "/archive99/2019/hello-world.html";
+    let request = request(path);
+    c.bench_function(&format!("regex set {}", path), |b| {
+        b.iter(|| black_box(router.find_route(&request).is_ok()))
+    });
+    c.bench_function(&format!("regex linear scan {}", path), |b| {
+        b.iter(|| {
+            black_box(
+                paths
+                    .iter()
+                    .filter(|candidate| candidate.is_match(path))
+                    .collect::<Vec<_>>(),
+            )
+        })
+    });
+}
+
+criterion_group!(benches, matching, regex_matching);
+criterion_main!(benches);
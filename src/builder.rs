@@ -1,5 +1,22 @@
+use super::middleware::ScopedMiddleware;
+use super::mount::Mount;
+use super::state::States;
+use super::tree::RouteTree;
+use super::Cors;
+use super::CorsError;
+use super::Guard;
+use super::Host;
+use super::Middleware;
+use super::Path;
+use super::PathError;
 use super::Route;
 use super::Router;
+use super::Scope;
+use super::State;
+use hyper::Method;
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
 
 /// Builder for a router
 ///
@@ -8,11 +25,33 @@ use super::Router;
 #[derive(Debug, Default)]
 pub struct RouterBuilder {
     routes: Vec<Route>,
+    states: States,
+    middleware: Vec<ScopedMiddleware>,
+    mounts: Vec<Mount>,
+    cors: Option<Cors>,
+    strict: bool,
+    priority: RoutePriority,
+    trailing_slash: TrailingSlash,
+    path_normalization: PathNormalization,
+    case_insensitive: bool,
+    host_fallback: HostFallback,
 }
 
 impl RouterBuilder {
     pub fn new() -> RouterBuilder {
-        RouterBuilder { routes: vec![] }
+        RouterBuilder {
+            routes: vec![],
+            states: States::default(),
+            middleware: vec![],
+            mounts: vec![],
+            cors: None,
+            strict: false,
+            priority: RoutePriority::Insertion,
+            trailing_slash: TrailingSlash::Strict,
+            path_normalization: PathNormalization::Raw,
+            case_insensitive: false,
+            host_fallback: HostFallback::Unrestricted,
+        }
     }
 
     /// Adds new `Route` for `Router` that is being built.
@@ -35,9 +74,530 @@ impl RouterBuilder {
         self
     }
 
-    pub fn build(self) -> Router {
-        Router {
+    /// Adds routes grouped in a `Scope`, prepending given prefix to their paths.
+    ///
+    /// Example:
+    ///
+    /// ```ignore
+    /// RouterBuilder::new().scope("/api/v1", |api| {
+    ///     api.add(Route::get("/users").using(list_users))
+    ///         .add(Route::post("/users").using(create_user))
+    /// });
+    /// ```
+    pub fn scope<F>(mut self, prefix: &str, build: F) -> RouterBuilder
+    where
+        F: FnOnce(Scope) -> Scope,
+    {
+        let scope = build(Scope::new(prefix));
+        self.routes.extend(scope.routes);
+        self.middleware.extend(scope.middleware);
+        self
+    }
+
+    /// Mounts another `Router` under given path prefix.
+    ///
+    /// Requests whose path starts with the prefix are passed to the mounted router when
+    /// none of the routes of this router matches them. The mounted router sees the path
+    /// with the prefix stripped, while the original URI is available in the request
+    /// extensions as `OriginalUri`. Its state and middleware apply only to its own routes
+    /// and run after the ones of this router.
+    ///
+    /// Example:
+    ///
+    /// ```ignore
+    /// let users = RouterBuilder::new()
+    ///     .add(Route::get("/").using(list_users))
+    ///     .add(Route::get("/:id").using(get_user))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// RouterBuilder::new().mount("/users", users);
+    /// ```
+    pub fn mount(mut self, prefix: &str, router: Router) -> RouterBuilder {
+        self.mounts.push(Mount {
+            prefix: Path::prefix(prefix),
+            host: None,
+            router,
+        });
+        self
+    }
+
+    /// Mounts another `Router` for requests to given host, like `api.example.com`
+    /// or `{tenant}.example.com`. Parameters captured from the host are added to `Params`.
+    ///
+    /// The mounted router sees the whole request path, and is tried in the order of mounts
+    /// like one mounted with `RouterBuilder::mount`. See `Host` for the syntax of the pattern.
+    ///
+    /// Example:
+    ///
+    /// ```ignore
+    /// RouterBuilder::new()
+    ///     .host("api.example.com", api_router)
+    ///     .host("{tenant}.example.com", tenant_router)
+    ///     .host_fallback(HostFallback::MisdirectedRequest);
+    /// ```
+    pub fn host(mut self, pattern: &str, router: Router) -> RouterBuilder {
+        self.mounts.push(Mount {
+            prefix: Path::prefix("/"),
+            host: Some(Host::new(pattern)),
+            router,
+        });
+        self
+    }
+
+    /// Sets how the `Router` treats requests whose host matches none of the hosts of its
+    /// routes and of routers mounted with `RouterBuilder::host`.
+    pub fn host_fallback(mut self, host_fallback: HostFallback) -> RouterBuilder {
+        self.host_fallback = host_fallback;
+        self
+    }
+
+    /// Adds state shared by all handlers of the `Router` that is being built.
+    ///
+    /// The state is stored once and inserted into the extensions of every request
+    /// as `State<T>`. Each type can be added once - adding the same type again replaces it.
+    ///
+    /// Example:
+    ///
+    /// ```ignore
+    /// use hyper_router::{Route, RouterBuilder, State};
+    ///
+    /// fn some_handler(request: Request<Body>) -> Response<Body> {
+    ///   let config = request.extensions().get::<State<Config>>().unwrap();
+    ///   // do something
+    /// }
+    ///
+    /// RouterBuilder::new()
+    ///     .with_state(Config::load())
+    ///     .add(Route::get("/").using(some_handler));
+    /// ```
+    pub fn with_state<T: Send + Sync + 'static>(mut self, state: T) -> RouterBuilder {
+        self.states.add(State::new(state));
+        self
+    }
+
+    /// Adds `Middleware` wrapping every request handled by the `Router` that is being built,
+    /// including requests that end with a routing error like 404.
+    ///
+    /// Middleware runs in the order it was added: the first one sees the request first
+    /// and the response last. It runs after the route has been found, so changes
+    /// to the request do not affect routing.
+    ///
+    /// Example:
+    ///
+    /// ```ignore
+    /// use hyper_router::{Next, ResponseFuture, RouterBuilder};
+    ///
+    /// fn log_requests(request: Request<Body>, next: Next) -> ResponseFuture {
+    ///   println!("{} {}", request.method(), request.uri());
+    ///   next.run(request)
+    /// }
+    ///
+    /// RouterBuilder::new().middleware(log_requests);
+    /// ```
+    pub fn middleware<M: Middleware>(mut self, middleware: M) -> RouterBuilder {
+        self.middleware
+            .push(ScopedMiddleware::new(Arc::new(middleware)));
+        self
+    }
+
+    /// Adds `Middleware` wrapping only requests whose path starts with given prefix.
+    ///
+    /// The prefix is matched on whole segments, so `/admin` covers `/admin` and `/admin/users`
+    /// but not `/administrator`. It keeps its place in the order of the router middleware
+    /// and runs before the middleware of the matched route.
+    ///
+    /// Example:
+    ///
+    /// ```ignore
+    /// RouterBuilder::new()
+    ///     .middleware_at("/admin", require_auth)
+    ///     .add(Route::get("/admin/users").using(list_users))
+    ///     .add(Route::get("/health").using(health));
+    /// ```
+    pub fn middleware_at<M: Middleware>(mut self, prefix: &str, middleware: M) -> RouterBuilder {
+        self.middleware
+            .push(ScopedMiddleware::at(prefix, Arc::new(middleware)));
+        self
+    }
+
+    /// Enables CORS for the `Router` that is being built.
+    ///
+    /// Preflight requests are answered before any middleware runs, with the methods
+    /// of the routes matching the request path unless `Cors::allow_methods` was used.
+    /// Other requests from allowed origins get the `Access-Control-*` headers added
+    /// to their responses. CORS settings of mounted routers are ignored.
+    ///
+    /// Example:
+    ///
+    /// ```ignore
+    /// RouterBuilder::new()
+    ///     .cors(Cors::new().allow_origin("https://example.com"))
+    ///     .add(Route::get("/users").using(list_users));
+    /// ```
+    pub fn cors(mut self, cors: Cors) -> RouterBuilder {
+        self.cors = Some(cors);
+        self
+    }
+
+    /// Makes `build` fail on duplicate and shadowed routes instead of only reporting them
+    /// in `Router::conflicts`.
+    ///
+    /// A route is shadowed when an earlier route with the same method matches every path
+    /// it matches, like `/user/.*` added before `/user/me`. Such routes are never chosen.
+    pub fn strict(mut self, strict: bool) -> RouterBuilder {
+        self.strict = strict;
+        self
+    }
+
+    /// Sets how the `Router` chooses between routes matching the same request.
+    ///
+    /// Only routes of this router are reordered - routes of mounted routers are still
+    /// checked after them, in the order set for the mounted router.
+    ///
+    /// Example:
+    ///
+    /// ```ignore
+    /// RouterBuilder::new()
+    ///     .priority(RoutePriority::Specificity)
+    ///     .add(Route::get("/user/:id").using(get_user))
+    ///     .add(Route::get("/user/me").using(get_current_user)); // chosen for `/user/me`
+    /// ```
+    pub fn priority(mut self, priority: RoutePriority) -> RouterBuilder {
+        self.priority = priority;
+        self
+    }
+
+    /// Sets how the `Router` treats requests whose path differs from a route only
+    /// by a trailing slash, like `/users/` for a `/users` route.
+    ///
+    /// The policy applies to the whole request path, including the routes of mounted routers.
+    ///
+    /// Example:
+    ///
+    /// ```ignore
+    /// RouterBuilder::new()
+    ///     .trailing_slash(TrailingSlash::RedirectToCanonical)
+    ///     .add(Route::get("/users").using(list_users)); // `/users/` is redirected to `/users`
+    /// ```
+    pub fn trailing_slash(mut self, trailing_slash: TrailingSlash) -> RouterBuilder {
+        self.trailing_slash = trailing_slash;
+        self
+    }
+
+    /// Sets how the `Router` treats percent-encoded characters, repeated slashes and dot
+    /// segments in request paths.
+    ///
+    /// With normalization the parameters given to handlers are percent-decoded too.
+    ///
+    /// Example:
+    ///
+    /// ```ignore
+    /// RouterBuilder::new()
+    ///     .path_normalization(PathNormalization::Normalize)
+    ///     .add(Route::get("/café").using(cafe)); // matches `/caf%C3%A9` and `/menu/../café`
+    /// ```
+    pub fn path_normalization(mut self, path_normalization: PathNormalization) -> RouterBuilder {
+        self.path_normalization = path_normalization;
+        self
+    }
+
+    /// Makes the static segments of all routes, mount prefixes and middleware prefixes
+    /// of the `Router` that is being built match regardless of case, like
+    /// `RouteBuilder::case_insensitive` does for a single route.
+    ///
+    /// Routes of mounted routers keep the setting of their own router.
+    pub fn case_insensitive(mut self, case_insensitive: bool) -> RouterBuilder {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Builds the `Router`.
+    ///
+    /// Returns an error naming the first route, mount or middleware prefix with an invalid
+    /// path pattern, the first route guard with an invalid header pattern, an invalid CORS
+    /// configuration, or in strict mode the first duplicate or shadowed route, so routes
+    /// read from configuration can be validated:
+    ///
+    /// ```ignore
+    /// match RouterBuilder::new().add(Route::get("/user/{id:int}").using(get_user)).build() {
+    ///     Ok(router) => serve(router),
+    ///     Err(error) => eprintln!("{}", error), // invalid path `/user/{id:int}` of route GET ...
+    /// }
+    /// ```
+    pub fn build(mut self) -> Result<Router, RouterError> {
+        if let Some(cors) = &self.cors {
+            cors.validate().map_err(RouterError::InvalidCors)?;
+        }
+        if self.case_insensitive {
+            let prefixes = self.mounts.iter_mut().map(|mount| &mut mount.prefix).chain(
+                self.middleware
+                    .iter_mut()
+                    .filter_map(|scoped| scoped.prefix.as_mut()),
+            );
+            let paths = self.routes.iter_mut().map(|route| &mut route.path);
+            paths.chain(prefixes).for_each(Path::make_case_insensitive);
+        }
+        for route in &self.routes {
+            if let Some(error) = route.path.error() {
+                return Err(RouterError::InvalidPath {
+                    route: route.name.clone(),
+                    method: Some(route.method.clone()),
+                    pattern: route.path.pattern().to_string(),
+                    error: error.clone(),
+                });
+            }
+            if let Some((pattern, error)) = route.guards.iter().find_map(Guard::error) {
+                return Err(RouterError::InvalidGuard {
+                    route: route.name.clone(),
+                    method: route.method.clone(),
+                    pattern: pattern.to_string(),
+                    error: error.clone(),
+                });
+            }
+        }
+        let prefixes = self.mounts.iter().map(|mount| &mount.prefix).chain(
+            self.middleware
+                .iter()
+                .filter_map(|scoped| scoped.prefix.as_ref()),
+        );
+        for prefix in prefixes {
+            if let Some(error) = prefix.error() {
+                return Err(RouterError::InvalidPath {
+                    route: None,
+                    method: None,
+                    pattern: prefix.pattern().to_string(),
+                    error: error.clone(),
+                });
+            }
+        }
+        if self.priority == RoutePriority::Specificity {
+            self.routes
+                .sort_by_cached_key(|route| route.path.specificity());
+        }
+        let conflicts = find_conflicts(&self.routes);
+        if self.strict {
+            if let Some(conflict) = conflicts.into_iter().next() {
+                return Err(conflict);
+            }
+            return self.build_router(vec![]);
+        }
+        self.build_router(conflicts)
+    }
+
+    fn build_router(self, conflicts: Vec<RouterError>) -> Result<Router, RouterError> {
+        let tree = RouteTree::new(&self.routes);
+        let mut hosts: Vec<Host> = vec![];
+        let route_hosts = self.routes.iter().filter_map(|route| route.host.as_ref());
+        let mount_hosts = self.mounts.iter().filter_map(|mount| mount.host.as_ref());
+        for host in route_hosts.chain(mount_hosts) {
+            if hosts.iter().all(|known| known.pattern() != host.pattern()) {
+                hosts.push(host.clone());
+            }
+        }
+        Ok(Router {
             routes: self.routes,
+            states: self.states,
+            middleware: self.middleware,
+            mounts: self.mounts,
+            cors: self.cors.map(Arc::new),
+            conflicts,
+            tree,
+            trailing_slash: self.trailing_slash,
+            path_normalization: self.path_normalization,
+            hosts,
+            host_fallback: self.host_fallback,
+        })
+    }
+}
+
+/// Order in which routes matching the same request are chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum RoutePriority {
+    /// The route that was added first is chosen.
+    #[default]
+    Insertion,
+    /// Routes are compared segment by segment: static text is chosen over typed parameters
+    /// like `{id:u64}`, those over other parameters like `:id`, and those over regular
+    /// expressions. Routes that are equally specific are chosen in the order they were added.
+    Specificity,
+}
+
+/// Policy for paths that differ from a route only by a trailing slash.
+///
+/// Redirects use 301 Moved Permanently for GET and HEAD requests and 308 Permanent Redirect
+/// for other methods, so that clients repeat them with the same method and body.
+/// Unless the policy is strict, a path whose other form has routes only for other methods
+/// gets 405 Method Not Allowed, like `Router::allowed_methods` reports.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TrailingSlash {
+    /// `/users` and `/users/` are different paths.
+    #[default]
+    Strict,
+    /// A path without a matching route is matched with the trailing slash added or removed.
+    Ignore,
+    /// A path without a matching route is redirected to the form with the trailing slash
+    /// added or removed, if that one has a route.
+    RedirectToCanonical,
+    /// Paths ending with a slash are matched like with `Ignore`, other paths are redirected
+    /// to their form with a trailing slash if either form has a route.
+    RedirectToSlash,
+}
+
+/// Policy for percent-encoded characters, empty segments and dot segments in request paths.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum PathNormalization {
+    /// Paths are matched as they were received.
+    #[default]
+    Raw,
+    /// Segments are percent-decoded, except for `%2F` which stays within its segment,
+    /// repeated slashes are collapsed and `.` and `..` segments, encoded or not, are resolved.
+    Normalize,
+    /// Segments are percent-decoded like with `Normalize`, but paths with repeated slashes
+    /// or dot segments are rejected with 400 Bad Request.
+    Reject,
+}
+
+/// Policy for requests whose host matches none of the hosts of a router.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum HostFallback {
+    /// The request is matched against the routes and mounts without a host.
+    #[default]
+    Unrestricted,
+    /// The request is rejected with 421 Misdirected Request, unless the router has no hosts.
+    MisdirectedRequest,
+}
+
+/// Finds routes that duplicate or are shadowed by an earlier route with the same method
+/// and no guards.
+fn find_conflicts(routes: &[Route]) -> Vec<RouterError> {
+    let mut conflicts = vec![];
+    for (index, route) in routes.iter().enumerate() {
+        let earlier = routes[..index]
+            .iter()
+            .filter(|earlier| earlier.method == route.method && earlier.guards.is_empty())
+            .filter(|earlier| match (&earlier.host, &route.host) {
+                (None, _) => true,
+                (Some(host), Some(other)) => host.pattern() == other.pattern(),
+                (Some(_), None) => false,
+            })
+            .find(|earlier| earlier.path.covers(&route.path));
+        if let Some(earlier) = earlier {
+            let pattern = route.path.pattern().to_string();
+            conflicts.push(if earlier.path.pattern() == pattern {
+                RouterError::DuplicateRoute {
+                    route: route.name.clone(),
+                    method: route.method.clone(),
+                    pattern,
+                }
+            } else {
+                RouterError::ShadowedRoute {
+                    route: route.name.clone(),
+                    method: route.method.clone(),
+                    pattern,
+                    shadowed_by: earlier.path.pattern().to_string(),
+                }
+            });
+        }
+    }
+    conflicts
+}
+
+/// Error returned when a `Router` cannot be built.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RouterError {
+    /// A path pattern is invalid. `route` is the name of the route and `method` its method,
+    /// both are `None` for mount and middleware prefixes.
+    InvalidPath {
+        route: Option<String>,
+        method: Option<Method>,
+        pattern: String,
+        error: PathError,
+    },
+    /// A route has the same method and pattern as an earlier one.
+    DuplicateRoute {
+        route: Option<String>,
+        method: Method,
+        pattern: String,
+    },
+    /// A route can never be chosen, as an earlier route with the same method
+    /// and pattern `shadowed_by` matches all of its paths.
+    ShadowedRoute {
+        route: Option<String>,
+        method: Method,
+        pattern: String,
+        shadowed_by: String,
+    },
+    /// The CORS configuration is invalid.
+    InvalidCors(CorsError),
+    /// The header pattern of a route guard is not a valid regular expression.
+    InvalidGuard {
+        route: Option<String>,
+        method: Method,
+        pattern: String,
+        error: PathError,
+    },
+}
+
+impl fmt::Display for RouterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RouterError::InvalidPath {
+                route,
+                method,
+                pattern,
+                error,
+            } => {
+                write!(f, "invalid path `{}`", pattern)?;
+                match (method, route) {
+                    (Some(method), Some(route)) => write!(f, " of route {} `{}`", method, route)?,
+                    (Some(method), None) => write!(f, " of route {}", method)?,
+                    _ => write!(f, " of prefix")?,
+                }
+                write!(f, ": {}", error)
+            }
+            RouterError::DuplicateRoute {
+                route,
+                method,
+                pattern,
+            } => {
+                write!(f, "duplicate route {} `{}`", method, pattern)?;
+                write_name(f, route)
+            }
+            RouterError::ShadowedRoute {
+                route,
+                method,
+                pattern,
+                shadowed_by,
+            } => {
+                write!(f, "route {} `{}`", method, pattern)?;
+                write_name(f, route)?;
+                write!(f, " is shadowed by earlier route `{}`", shadowed_by)
+            }
+            RouterError::InvalidCors(error) => write!(f, "invalid CORS configuration: {}", error),
+            RouterError::InvalidGuard {
+                route,
+                method,
+                pattern,
+                error,
+            } => {
+                write!(
+                    f,
+                    "invalid header pattern `{}` of route {}",
+                    pattern, method
+                )?;
+                write_name(f, route)?;
+                write!(f, ": {}", error)
+            }
         }
     }
 }
+
+impl Error for RouterError {}
+
+fn write_name(f: &mut fmt::Formatter, route: &Option<String>) -> fmt::Result {
+    match route {
+        Some(name) => write!(f, " named `{}`", name),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,298 @@
+use hyper::header::{
+    HeaderName, HeaderValue, ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_HEADERS,
+    ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS,
+    ACCESS_CONTROL_MAX_AGE, ACCESS_CONTROL_REQUEST_HEADERS, ACCESS_CONTROL_REQUEST_METHOD, ORIGIN,
+    VARY,
+};
+use hyper::{Body, Method, Request, Response, StatusCode};
+use regex::Regex;
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+/// Cross-Origin Resource Sharing configuration of a `Router`.
+///
+/// Added with `RouterBuilder::cors`. `RouterService` answers preflight requests on its own,
+/// before any middleware runs, and adds `Access-Control-*` headers to the responses
+/// for other requests coming from allowed origins:
+///
+/// ```no_run
+/// use hyper_router::{Cors, RouterBuilder};
+/// use std::time::Duration;
+///
+/// let cors = Cors::new()
+///     .allow_origin("https://example.com")
+///     .allow_origin_regex(r"https://[a-z]+\.example\.com")
+///     .allow_headers(&["content-type", "authorization"])
+///     .expose_headers(&["x-request-id"])
+///     .allow_credentials(true)
+///     .max_age(Duration::from_secs(3600));
+///
+/// RouterBuilder::new().cors(cors);
+/// ```
+///
+/// By default every origin is allowed, preflight requests are answered with the methods
+/// of the routes matching the request path and the headers requested by the browser.
+/// Credentials can only be allowed together with an explicit list or pattern of origins,
+/// otherwise `RouterBuilder::build` returns `CorsError::CredentialsWithAnyOrigin`.
+#[derive(Debug, Clone, Default)]
+pub struct Cors {
+    origins: Vec<String>,
+    origin_patterns: Vec<Regex>,
+    methods: Option<Vec<Method>>,
+    headers: Option<Vec<HeaderName>>,
+    exposed_headers: Vec<HeaderName>,
+    credentials: bool,
+    max_age: Option<Duration>,
+    /// Invalid settings, reported by `RouterBuilder::build`.
+    errors: Vec<CorsError>,
+}
+
+impl Cors {
+    pub fn new() -> Cors {
+        Cors::default()
+    }
+
+    /// Allows requests from given origin, like `https://example.com`.
+    ///
+    /// Once any origin is added, only the added origins are allowed.
+    pub fn allow_origin(mut self, origin: &str) -> Cors {
+        self.origins.push(origin.to_string());
+        self
+    }
+
+    /// Allows requests from each of given origins.
+    pub fn allow_origins(self, origins: &[&str]) -> Cors {
+        origins
+            .iter()
+            .fold(self, |cors, origin| cors.allow_origin(origin))
+    }
+
+    /// Allows requests from origins matching given regular expression.
+    ///
+    /// Like with `Path::new`, the whole origin has to match - `^` and `$` are inserted for you.
+    /// An invalid regular expression does not panic here - it is reported by
+    /// `RouterBuilder::build`.
+    pub fn allow_origin_regex(mut self, pattern: &str) -> Cors {
+        match Regex::new(&format!("^(?:{})$", pattern)) {
+            Ok(regex) => self.origin_patterns.push(regex),
+            Err(error) => self.errors.push(CorsError::InvalidOriginRegex {
+                pattern: pattern.to_string(),
+                error: error.to_string(),
+            }),
+        }
+        self
+    }
+
+    /// Sets methods allowed in preflight responses instead of the methods of matching routes.
+    pub fn allow_methods(mut self, methods: &[Method]) -> Cors {
+        self.methods = Some(methods.to_vec());
+        self
+    }
+
+    /// Sets headers allowed in preflight responses instead of the headers requested by the browser.
+    ///
+    /// Invalid header names are reported by `RouterBuilder::build`.
+    pub fn allow_headers(mut self, headers: &[&str]) -> Cors {
+        let names = self.header_names(headers);
+        self.headers = Some(names);
+        self
+    }
+
+    /// Sets response headers exposed to the browser.
+    ///
+    /// Invalid header names are reported by `RouterBuilder::build`.
+    pub fn expose_headers(mut self, headers: &[&str]) -> Cors {
+        self.exposed_headers = self.header_names(headers);
+        self
+    }
+
+    fn header_names(&mut self, headers: &[&str]) -> Vec<HeaderName> {
+        let mut names = vec![];
+        for header in headers {
+            match HeaderName::from_bytes(header.as_bytes()) {
+                Ok(name) => names.push(name),
+                Err(_) => self
+                    .errors
+                    .push(CorsError::InvalidHeader(header.to_string())),
+            }
+        }
+        names
+    }
+
+    /// Allows requests with credentials like cookies.
+    ///
+    /// Requires origins to be set with `allow_origin`, `allow_origins` or `allow_origin_regex`.
+    pub fn allow_credentials(mut self, allow: bool) -> Cors {
+        self.credentials = allow;
+        self
+    }
+
+    /// Sets how long browsers may cache preflight responses.
+    pub fn max_age(mut self, max_age: Duration) -> Cors {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Checks the configuration when the router is built.
+    pub(crate) fn validate(&self) -> Result<(), CorsError> {
+        if let Some(error) = self.errors.first() {
+            return Err(error.clone());
+        }
+        if self.credentials && self.allows_any_origin() {
+            return Err(CorsError::CredentialsWithAnyOrigin);
+        }
+        Ok(())
+    }
+
+    fn allows_any_origin(&self) -> bool {
+        self.origins.is_empty() && self.origin_patterns.is_empty()
+    }
+
+    /// Returns the `Access-Control-Allow-Origin` value for given request, or `None`
+    /// if it is not a CORS request or its origin is not allowed.
+    pub(crate) fn allowed_origin(&self, request: &Request<Body>) -> Option<HeaderValue> {
+        let origin = request.headers().get(ORIGIN)?;
+        if self.allows_any_origin() {
+            return Some(HeaderValue::from_static("*"));
+        }
+        let origin_str = origin.to_str().ok()?;
+        let allowed = self.origins.iter().any(|allowed| allowed == origin_str)
+            || self
+                .origin_patterns
+                .iter()
+                .any(|pattern| pattern.is_match(origin_str));
+        if allowed {
+            Some(origin.clone())
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn is_preflight(&self, request: &Request<Body>) -> bool {
+        request.method() == Method::OPTIONS
+            && request
+                .headers()
+                .contains_key(ACCESS_CONTROL_REQUEST_METHOD)
+    }
+
+    /// Builds the response to a preflight request, given the methods the router knows for
+    /// its path. Returns `None` if the requested method is not allowed, in which case
+    /// the request is routed like any other OPTIONS request, without CORS headers.
+    pub(crate) fn preflight_response(
+        &self,
+        request: &Request<Body>,
+        origin: &HeaderValue,
+        router_methods: Vec<Method>,
+    ) -> Option<Response<Body>> {
+        if router_methods.is_empty() {
+            return None;
+        }
+        let requested_method = request
+            .headers()
+            .get(ACCESS_CONTROL_REQUEST_METHOD)?
+            .to_str()
+            .ok()?
+            .parse::<Method>()
+            .ok()?;
+        let methods = self.methods.clone().unwrap_or(router_methods);
+        if !methods.contains(&requested_method) {
+            return None;
+        }
+
+        let mut response = Response::new(Body::empty());
+        *response.status_mut() = StatusCode::NO_CONTENT;
+        self.add_origin_headers(origin, &mut response);
+        let headers = response.headers_mut();
+        let methods: Vec<&str> = methods.iter().map(Method::as_str).collect();
+        headers.insert(
+            ACCESS_CONTROL_ALLOW_METHODS,
+            HeaderValue::from_str(&methods.join(", ")).expect("Invalid method list"),
+        );
+        match &self.headers {
+            Some(allowed) => {
+                if !allowed.is_empty() {
+                    headers.insert(ACCESS_CONTROL_ALLOW_HEADERS, header_list(allowed));
+                }
+            }
+            None => {
+                if let Some(requested) = request.headers().get(ACCESS_CONTROL_REQUEST_HEADERS) {
+                    headers.insert(ACCESS_CONTROL_ALLOW_HEADERS, requested.clone());
+                }
+            }
+        }
+        if let Some(max_age) = self.max_age {
+            headers.insert(ACCESS_CONTROL_MAX_AGE, HeaderValue::from(max_age.as_secs()));
+        }
+        Some(response)
+    }
+
+    /// Adds CORS headers to the response for an actual (not preflight) request.
+    pub(crate) fn add_headers(&self, origin: &HeaderValue, response: &mut Response<Body>) {
+        self.add_origin_headers(origin, response);
+        if !self.exposed_headers.is_empty() {
+            response.headers_mut().insert(
+                ACCESS_CONTROL_EXPOSE_HEADERS,
+                header_list(&self.exposed_headers),
+            );
+        }
+    }
+
+    /// Adds `Vary: Origin` to a response without CORS headers when the allowed origins are
+    /// restricted, as responses to requests from other origins differ.
+    pub(crate) fn add_vary(&self, response: &mut Response<Body>) {
+        if !self.allows_any_origin() {
+            response
+                .headers_mut()
+                .append(VARY, HeaderValue::from_static("Origin"));
+        }
+    }
+
+    fn add_origin_headers(&self, origin: &HeaderValue, response: &mut Response<Body>) {
+        let headers = response.headers_mut();
+        headers.insert(ACCESS_CONTROL_ALLOW_ORIGIN, origin.clone());
+        if origin != "*" {
+            headers.append(VARY, HeaderValue::from_static("Origin"));
+        }
+        if self.credentials {
+            headers.insert(
+                ACCESS_CONTROL_ALLOW_CREDENTIALS,
+                HeaderValue::from_static("true"),
+            );
+        }
+    }
+}
+
+/// Joins header names into a header value, which cannot fail as header names are
+/// valid header values.
+fn header_list(names: &[HeaderName]) -> HeaderValue {
+    let names: Vec<&str> = names.iter().map(HeaderName::as_str).collect();
+    HeaderValue::from_str(&names.join(", ")).expect("Header names should be valid values")
+}
+
+/// Error returned for an invalid `Cors` configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CorsError {
+    /// Credentials are allowed without restricting the allowed origins.
+    CredentialsWithAnyOrigin,
+    /// An origin pattern is not a valid regular expression.
+    InvalidOriginRegex { pattern: String, error: String },
+    /// An allowed or exposed header is not a valid header name.
+    InvalidHeader(String),
+}
+
+impl fmt::Display for CorsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CorsError::CredentialsWithAnyOrigin => {
+                write!(f, "credentials cannot be allowed for any origin")
+            }
+            CorsError::InvalidOriginRegex { pattern, error } => {
+                write!(f, "invalid origin pattern `{}`: {}", pattern, error)
+            }
+            CorsError::InvalidHeader(header) => write!(f, "invalid header name `{}`", header),
+        }
+    }
+}
+
+impl Error for CorsError {}
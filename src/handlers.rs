@@ -1,24 +1,39 @@
-use hyper::header::{CONTENT_LENGTH, CONTENT_TYPE};
-use hyper::{Body, Request, Response, StatusCode};
+use futures::Future;
+use hyper::body::Payload;
+use hyper::header::{HeaderValue, ALLOW, CONTENT_LENGTH, CONTENT_TYPE, LOCATION};
+use hyper::{Body, Method, Request, Response, StatusCode};
+use std::sync::Arc;
+
+use crate::{Handler, ResponseFuture};
 
 pub fn default_404_handler(_: Request<Body>) -> Response<Body> {
     let body = "page not found";
-    make_response(&body, StatusCode::NOT_FOUND)
+    make_response(body, StatusCode::NOT_FOUND)
+}
+
+pub fn bad_request_handler(_: Request<Body>) -> Response<Body> {
+    let body = "bad request";
+    make_response(body, StatusCode::BAD_REQUEST)
+}
+
+pub fn misdirected_request_handler(_: Request<Body>) -> Response<Body> {
+    let body = "misdirected request";
+    make_response(body, StatusCode::MISDIRECTED_REQUEST)
 }
 
 pub fn method_not_supported_handler(_: Request<Body>) -> Response<Body> {
     let body = "method not supported";
-    make_response(&body, StatusCode::METHOD_NOT_ALLOWED)
+    make_response(body, StatusCode::METHOD_NOT_ALLOWED)
 }
 
 pub fn internal_server_error_handler(_: Request<Body>) -> Response<Body> {
     let body = "internal server error";
-    make_response(&body, StatusCode::INTERNAL_SERVER_ERROR)
+    make_response(body, StatusCode::INTERNAL_SERVER_ERROR)
 }
 
 pub fn not_implemented_handler(_: Request<Body>) -> Response<Body> {
     let body = "not implemented";
-    make_response(&body, StatusCode::NOT_IMPLEMENTED)
+    make_response(body, StatusCode::NOT_IMPLEMENTED)
 }
 
 fn make_response(body: &'static str, status: StatusCode) -> Response<Body> {
@@ -29,3 +44,54 @@ fn make_response(body: &'static str, status: StatusCode) -> Response<Body> {
         .body(Body::from(body))
         .expect("Failed to construct response")
 }
+
+/// Builds the value of the `Allow` header listing given methods.
+pub fn allow_header(methods: &[Method]) -> HeaderValue {
+    let methods: Vec<&str> = methods.iter().map(Method::as_str).collect();
+    HeaderValue::from_str(&methods.join(", ")).expect("Method names are valid header values")
+}
+
+/// Creates handler answering OPTIONS requests with 204 No Content and an `Allow` header
+/// listing given methods.
+pub fn options_handler(allowed_methods: Vec<Method>) -> Arc<dyn Handler> {
+    let allow = allow_header(&allowed_methods);
+    Arc::new(move |_: Request<Body>| {
+        Response::builder()
+            .status(StatusCode::NO_CONTENT)
+            .header(ALLOW, allow.clone())
+            .body(Body::empty())
+            .expect("Failed to construct response")
+    })
+}
+
+/// Creates handler redirecting requests to given location with given status code.
+pub fn redirect_handler(status_code: StatusCode, location: String) -> Arc<dyn Handler> {
+    Arc::new(move |_: Request<Body>| {
+        Response::builder()
+            .status(status_code)
+            .header(LOCATION, location.as_str())
+            .header(CONTENT_LENGTH, 0u64)
+            .body(Body::empty())
+            .expect("Failed to construct response")
+    })
+}
+
+/// Wraps given handler so that it responds without a body, keeping the headers.
+///
+/// This is how GET handlers serve HEAD requests. `Content-Length` is set from the original
+/// body if the handler did not set it and the length is known.
+pub fn without_body(handler: Arc<dyn Handler>) -> Arc<dyn Handler> {
+    Arc::new(move |request: Request<Body>| -> ResponseFuture {
+        Box::new(handler.handle(request).map(|response| {
+            let (mut parts, body) = response.into_parts();
+            if !parts.headers.contains_key(CONTENT_LENGTH) {
+                if let Some(length) = body.content_length() {
+                    parts
+                        .headers
+                        .insert(CONTENT_LENGTH, HeaderValue::from(length));
+                }
+            }
+            Response::from_parts(parts, Body::empty())
+        }))
+    })
+}
@@ -0,0 +1,114 @@
+use hyper::header::HOST;
+use hyper::{Body, Request};
+use regex::Regex;
+
+use crate::path::is_identifier;
+use crate::Params;
+
+/// Host a `Route` or a mounted `Router` is restricted to, like `api.example.com`.
+///
+/// A label written as `{name}` or `:name` matches any single label and captures it as
+/// a parameter called `name`, a label written as `*` matches any single label without
+/// capturing it:
+///
+/// ```no_run
+/// use hyper_router::Host;
+/// Host::new("{tenant}.example.com");
+/// Host::new("*.static.example.com");
+/// ```
+///
+/// Hosts are matched regardless of case, without the port.
+#[derive(Debug, Clone)]
+pub struct Host {
+    matcher: Regex,
+    pattern: String,
+}
+
+impl Host {
+    pub fn new(pattern: &str) -> Host {
+        let mut names: Vec<&str> = vec![];
+        let labels: Vec<String> = pattern
+            .trim_end_matches('.')
+            .split('.')
+            .map(|label| match param_name(label) {
+                Some(name) if !names.contains(&name) => {
+                    names.push(name);
+                    format!("(?P<{}>[^.]+)", name)
+                }
+                Some(_) => "[^.]+".to_string(),
+                None if label == "*" => "[^.]+".to_string(),
+                None => regex::escape(&label.to_ascii_lowercase()),
+            })
+            .collect();
+        Host {
+            matcher: Regex::new(&format!("^{}$", labels.join(r"\."))).unwrap(),
+            pattern: pattern.to_string(),
+        }
+    }
+
+    /// Returns the pattern this host was created from.
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Checks if given host, without a port, matches this one.
+    pub fn is_match(&self, host: &str) -> bool {
+        self.matches_request_host(&normalize(host))
+    }
+
+    /// Returns parameters captured from given host or `None` if the host does not match.
+    pub fn params(&self, host: &str) -> Option<Params> {
+        self.request_host_params(&normalize(host))
+    }
+
+    /// Checks if a host returned by `request_host`, already normalized, matches this one.
+    pub(crate) fn matches_request_host(&self, host: &str) -> bool {
+        self.matcher.is_match(host)
+    }
+
+    /// Returns parameters captured from a host returned by `request_host`.
+    pub(crate) fn request_host_params(&self, host: &str) -> Option<Params> {
+        let captures = self.matcher.captures(host)?;
+        let mut params = Params::new();
+        for name in self.matcher.capture_names().flatten() {
+            if let Some(value) = captures.name(name) {
+                params.insert(name, value.as_str());
+            }
+        }
+        Some(params)
+    }
+}
+
+fn param_name(label: &str) -> Option<&str> {
+    let name = match label.strip_prefix(':') {
+        Some(name) => name,
+        None => label.strip_prefix('{')?.strip_suffix('}')?,
+    };
+    if is_identifier(name) {
+        Some(name)
+    } else {
+        None
+    }
+}
+
+fn normalize(host: &str) -> String {
+    host.trim_end_matches('.').to_ascii_lowercase()
+}
+
+/// Returns the host of given request, without the port, taken from the request URI
+/// or the `Host` header. Requests without a host get an empty one.
+pub(crate) fn request_host(request: &Request<Body>) -> String {
+    if let Some(host) = request.uri().host() {
+        return normalize(host);
+    }
+    let authority = request
+        .headers()
+        .get(HOST)
+        .and_then(|host| host.to_str().ok())
+        .unwrap_or("");
+    let host = match authority.find(']') {
+        Some(end) if authority.starts_with('[') => &authority[..=end],
+        _ => authority.split(':').next().unwrap_or(""),
+    };
+    normalize(host)
+}
@@ -40,7 +40,8 @@
 //!     let router = RouterBuilder::new()
 //!         .add(Route::get("/greet").using(basic_handler))
 //!         .add(Route::from(Method::PATCH, "/asd").using(basic_handler))
-//!         .build();
+//!         .build()
+//!         .expect("Failed to build the router");
 //!
 //!     Ok(RouterService::new(router))
 //! }
@@ -62,10 +63,17 @@
 //! ## Things to note
 //!
 //! * `Path::new` method accepts regular expressions so you can match every path you please.
-//! * If you have request matching multiple paths the one that was first `add`ed will be chosen.
+//! * Segments written as `:name` (and named regex groups) are captured into `Params`, which
+//!   `RouterService` stores in the request extensions. A last segment written as `*name`
+//!   (or `**`, captured as `tail`) captures the rest of the path, including slashes.
+//! * If you have request matching multiple paths the one that was first `add`ed will be chosen,
+//!   unless the router is built with `RoutePriority::Specificity`. Routes that can never be
+//!   chosen are listed by `Router::conflicts`.
+//! * Paths are matched as received, percent-encoding included, unless the router is built with
+//!   a `PathNormalization` policy.
 //! * This library is in an early stage of development so there may be breaking changes comming
-//! (but I'll try as hard as I can not to break backwards compatibility or break it just a little -
-//! I promise I'll try!).
+//!   (but I'll try as hard as I can not to break backwards compatibility or break it just a little -
+//!   I promise I'll try!).
 //!
 //! # Waiting for your feedback
 //!
@@ -78,31 +86,129 @@
 extern crate futures;
 extern crate hyper;
 
-use futures::future::FutureResult;
-use hyper::header::CONTENT_LENGTH;
+use futures::Future;
+use hyper::header::{ALLOW, CONTENT_LENGTH};
 use hyper::service::Service;
-use hyper::{Body, Request, Response};
+use hyper::{Body, Request, Response, Uri};
 
 use hyper::Method;
 use hyper::StatusCode;
+use std::borrow::Cow;
+use std::cell::OnceCell;
+use std::sync::Arc;
 
 mod builder;
+mod cors;
 pub mod handlers;
+mod host;
+mod middleware;
+mod mount;
+mod normalize;
+mod params;
 mod path;
 pub mod route;
+mod scope;
+mod state;
+mod tree;
 
-pub use self::builder::RouterBuilder;
-pub use self::path::Path;
+pub use self::builder::{
+    HostFallback, PathNormalization, RoutePriority, RouterBuilder, RouterError, TrailingSlash,
+};
+pub use self::cors::{Cors, CorsError};
+pub use self::host::Host;
+pub use self::middleware::{Middleware, Next};
+pub use self::mount::OriginalUri;
+pub use self::params::{ParamError, Params};
+pub use self::path::{Path, PathError, UrlError};
+pub use self::route::Guard;
 pub use self::route::Route;
 pub use self::route::RouteBuilder;
+pub use self::scope::Scope;
+pub use self::state::State;
 
-pub type Handler = fn(Request<Body>) -> Response<Body>;
+pub type ResponseFuture = Box<dyn Future<Item = Response<Body>, Error = hyper::Error> + Send>;
 pub type HttpResult<T> = Result<T, StatusCode>;
 
+/// Request handler of a `Route`.
+///
+/// It is implemented for every function and closure that accepts Hyper's `Request` and
+/// returns a `Response`, an `HttpResult<Response>` or a `ResponseFuture`, so handlers can
+/// capture shared state:
+///
+/// ```no_run
+/// use hyper::{Body, Request, Response};
+/// use hyper_router::Route;
+/// use std::sync::Arc;
+///
+/// let greeting = Arc::new("Hello".to_string());
+/// Route::get("/greet").using(move |_: Request<Body>| Response::new(Body::from(greeting.to_string())));
+/// ```
+pub trait Handler: Send + Sync + 'static {
+    fn handle(&self, request: Request<Body>) -> ResponseFuture;
+}
+
+impl<F, R> Handler for F
+where
+    F: Fn(Request<Body>) -> R + Send + Sync + 'static,
+    R: IntoResponseFuture,
+{
+    fn handle(&self, request: Request<Body>) -> ResponseFuture {
+        self(request).into_response_future()
+    }
+}
+
+/// Result of a handler that can be turned into a `ResponseFuture`.
+pub trait IntoResponseFuture {
+    fn into_response_future(self) -> ResponseFuture;
+}
+
+impl IntoResponseFuture for Response<Body> {
+    fn into_response_future(self) -> ResponseFuture {
+        Box::new(futures::future::ok(self))
+    }
+}
+
+impl IntoResponseFuture for ResponseFuture {
+    fn into_response_future(self) -> ResponseFuture {
+        self
+    }
+}
+
+/// An error status becomes an empty response with that status, so handlers can propagate
+/// errors like the ones of `Params::parse` with `?`.
+impl IntoResponseFuture for HttpResult<Response<Body>> {
+    fn into_response_future(self) -> ResponseFuture {
+        let response = self.unwrap_or_else(|status_code| {
+            let mut response = Response::new(Body::empty());
+            *response.status_mut() = status_code;
+            response
+        });
+        response.into_response_future()
+    }
+}
+
 /// This is the one. The router.
 #[derive(Debug)]
 pub struct Router {
     routes: Vec<Route>,
+    states: state::States,
+    middleware: Vec<middleware::ScopedMiddleware>,
+    mounts: Vec<mount::Mount>,
+    cors: Option<Arc<Cors>>,
+    conflicts: Vec<RouterError>,
+    tree: tree::RouteTree,
+    trailing_slash: TrailingSlash,
+    path_normalization: PathNormalization,
+    hosts: Vec<Host>,
+    host_fallback: HostFallback,
+}
+
+/// Route found for a request together with the parameters captured from its path.
+#[derive(Debug)]
+pub struct RouteMatch<'a> {
+    pub route: &'a Route,
+    pub params: Params,
+    mounts: Vec<mount::MountMatch<'a>>,
 }
 
 impl Router {
@@ -112,13 +218,29 @@ impl Router {
     /// If the request does not match any route than default 404 handler is returned.
     /// If the request match some routes but http method does not match (used GET but routes are
     /// defined for POST) than default method not supported handler is returned.
-    pub fn find_handler_with_defaults(&self, request: &Request<Body>) -> Handler {
-        let matching_routes = self.find_matching_routes(request.uri().path());
-        match matching_routes.len() {
-            x if x == 0 => handlers::default_404_handler,
-            _ => self
-                .find_for_method(&matching_routes, request.method())
-                .unwrap_or(handlers::method_not_supported_handler),
+    /// OPTIONS requests without a matching OPTIONS route get a handler listing allowed methods.
+    pub fn find_handler_with_defaults(&self, request: &Request<Body>) -> Arc<dyn Handler> {
+        match self.find_route(request) {
+            Ok(route_match) => route_match.route.handler.clone(),
+            Err(status_code) if self.answers_options(request, status_code) => {
+                handlers::options_handler(self.request_methods(request))
+            }
+            Err(StatusCode::NOT_FOUND) => Arc::new(handlers::default_404_handler),
+            Err(StatusCode::BAD_REQUEST) => Arc::new(handlers::bad_request_handler),
+            Err(StatusCode::MISDIRECTED_REQUEST) => Arc::new(handlers::misdirected_request_handler),
+            Err(status_code) if is_redirect(status_code) => handlers::redirect_handler(
+                status_code,
+                self.trailing_slash_location(request.uri())
+                    .unwrap_or_default(),
+            ),
+            Err(_) => {
+                let allow = handlers::allow_header(&self.request_methods(request));
+                Arc::new(move |request: Request<Body>| {
+                    let mut response = handlers::method_not_supported_handler(request);
+                    response.headers_mut().insert(ALLOW, allow.clone());
+                    response
+                })
+            }
         }
     }
 
@@ -126,33 +248,408 @@ impl Router {
     ///
     /// It returns handler if it's found or `StatusCode` for error.
     /// This method may return `NotFound`, `MethodNotAllowed` or `NotImplemented`
-    /// status codes.
-    pub fn find_handler(&self, request: &Request<Body>) -> HttpResult<Handler> {
-        let matching_routes = self.find_matching_routes(request.uri().path());
-        match matching_routes.len() {
-            x if x == 0 => Err(StatusCode::NOT_FOUND),
-            _ => self
-                .find_for_method(&matching_routes, request.method())
-                .map(Ok)
-                .unwrap_or(Err(StatusCode::METHOD_NOT_ALLOWED)),
+    /// status codes. With a redirecting `TrailingSlash` policy it returns `MovedPermanently`
+    /// or `PermanentRedirect` for paths that should be redirected to
+    /// `Router::trailing_slash_location`. Paths rejected by the `PathNormalization` policy
+    /// return `BadRequest`, and hosts rejected by the `HostFallback` policy return
+    /// `MisdirectedRequest`.
+    pub fn find_handler(&self, request: &Request<Body>) -> HttpResult<Arc<dyn Handler>> {
+        self.find_route(request)
+            .map(|route_match| route_match.route.handler.clone())
+    }
+
+    /// Finds route for given Hyper request.
+    ///
+    /// Works like `find_handler` but also returns the parameters captured from the request path.
+    /// Routes of this router are checked before the routes of mounted routers.
+    ///
+    /// HEAD requests are matched to GET routes if there is no HEAD route for the path.
+    /// Routes restricted to a host are only matched by requests to that host, and routes
+    /// with guards only by requests passing all of them.
+    pub fn find_route(&self, request: &Request<Body>) -> HttpResult<RouteMatch<'_>> {
+        let path = self.normalized_path(request.uri().path())?;
+        self.find_route_in(&path, request.method(), Some(&Target::new(request)))
+    }
+
+    /// Returns the path routes are matched against for given request path, according to
+    /// the `PathNormalization` policy of the router.
+    ///
+    /// It returns `BadRequest` for paths with invalid percent-encoding and, with
+    /// `PathNormalization::Reject`, for paths with repeated slashes or dot segments.
+    pub fn normalized_path<'a>(&self, path: &'a str) -> HttpResult<Cow<'a, str>> {
+        normalize::normalize(path, self.path_normalization)
+    }
+
+    /// Returns the normalized path of the request or its raw path if it was rejected.
+    fn matched_path<'a>(&self, request: &'a Request<Body>) -> Cow<'a, str> {
+        let path = request.uri().path();
+        self.normalized_path(path).unwrap_or(Cow::Borrowed(path))
+    }
+
+    /// Returns the methods allowed for the request, taking its host and guards into account.
+    fn request_methods(&self, request: &Request<Body>) -> Vec<Method> {
+        self.methods_at(&self.matched_path(request), Some(&Target::new(request)))
+    }
+
+    /// Finds the route for given path and method, `None` target matching routes of any host
+    /// and ignoring guards.
+    fn find_route_in(
+        &self,
+        path: &str,
+        method: &Method,
+        target: Option<&Target>,
+    ) -> HttpResult<RouteMatch<'_>> {
+        if let Some(target) = target {
+            if self.host_fallback == HostFallback::MisdirectedRequest
+                && !self.hosts.is_empty()
+                && !self
+                    .hosts
+                    .iter()
+                    .any(|known| known.matches_request_host(target.host()))
+            {
+                return Err(StatusCode::MISDIRECTED_REQUEST);
+            }
+        }
+        let mut result = self.find_trailing_slash_route(path, method, target);
+        if self.path_normalization != PathNormalization::Raw {
+            if let Ok(route_match) = &mut result {
+                route_match.params =
+                    normalize::decode_params(std::mem::take(&mut route_match.params));
+            }
+        }
+        result
+    }
+
+    fn find_trailing_slash_route(
+        &self,
+        path: &str,
+        method: &Method,
+        target: Option<&Target>,
+    ) -> HttpResult<RouteMatch<'_>> {
+        let result = self.find_route_for(path, method, target);
+        let redirects_to_slash =
+            self.trailing_slash == TrailingSlash::RedirectToSlash && !path.ends_with('/');
+        if self.trailing_slash == TrailingSlash::Strict || (result.is_ok() && !redirects_to_slash) {
+            return result;
+        }
+        let toggled = match toggle_trailing_slash(path) {
+            Some(toggled) => toggled,
+            None => return result,
+        };
+        let redirect = match method {
+            &Method::GET | &Method::HEAD => StatusCode::MOVED_PERMANENTLY,
+            _ => StatusCode::PERMANENT_REDIRECT,
+        };
+        match self.trailing_slash {
+            TrailingSlash::Strict => result,
+            TrailingSlash::Ignore => match result {
+                Err(StatusCode::NOT_FOUND) => self.find_route_for(&toggled, method, target),
+                result => result,
+            },
+            TrailingSlash::RedirectToCanonical => match result {
+                Err(StatusCode::NOT_FOUND) => self
+                    .find_route_for(&toggled, method, target)
+                    .and(Err(redirect)),
+                result => result,
+            },
+            TrailingSlash::RedirectToSlash if path.ends_with('/') => match result {
+                Err(StatusCode::NOT_FOUND) => self.find_route_for(&toggled, method, target),
+                result => result,
+            },
+            TrailingSlash::RedirectToSlash => match result {
+                Err(StatusCode::NOT_FOUND) => self
+                    .find_route_for(&toggled, method, target)
+                    .and(Err(redirect)),
+                Err(_) if self.find_route_for(&toggled, method, target).is_err() => result,
+                _ => Err(redirect),
+            },
+        }
+    }
+
+    /// Returns the URI (path and query) a request with given URI is redirected to when
+    /// `find_route` returns a redirect status, that is the path with its trailing slash
+    /// added or removed.
+    pub fn trailing_slash_location(&self, uri: &Uri) -> Option<String> {
+        let path = self.normalized_path(uri.path()).ok()?;
+        let path = toggle_trailing_slash(&normalize::encode_path(&path))?;
+        Some(match uri.query() {
+            Some(query) => format!("{}?{}", path, query),
+            None => path,
+        })
+    }
+
+    fn find_route_for(
+        &self,
+        path: &str,
+        method: &Method,
+        target: Option<&Target>,
+    ) -> HttpResult<RouteMatch<'_>> {
+        match self.find_route_at(path, method, target) {
+            Err(_) if method == Method::HEAD => self.find_route_at(path, &Method::GET, target),
+            result => result,
         }
     }
 
-    /// Returns vector of `Route`s that match to given path.
+    /// Returns vector of `Route`s that match to given path, including routes of mounted routers.
+    ///
+    /// Routes restricted to a host are returned regardless of their host.
     pub fn find_matching_routes(&self, request_path: &str) -> Vec<&Route> {
-        self.routes
-            .iter()
-            .filter(|route| route.path.matcher.is_match(&request_path))
-            .collect()
+        self.matching_routes(request_path, None)
     }
 
-    fn find_for_method(&self, routes: &[&Route], method: &Method) -> Option<Handler> {
-        let method = method.clone();
+    fn matching_routes(&self, request_path: &str, target: Option<&Target>) -> Vec<&Route> {
+        let mut routes: Vec<&Route> = self
+            .tree
+            .matching(&self.routes, request_path)
+            .into_iter()
+            .map(|index| &self.routes[index])
+            .filter(|route| accepts(route, target))
+            .collect();
+        for mount in &self.mounts {
+            if !host_matches(mount.host.as_ref(), target) {
+                continue;
+            }
+            if let Some((prefix_len, _)) = mount.prefix.prefix_match(request_path) {
+                let path = mount::strip_prefix(request_path, prefix_len);
+                routes.extend(mount.router.matching_routes(path, target));
+            }
+        }
         routes
-            .iter()
-            .find(|route| route.method == method)
-            .map(|route| route.handler)
     }
+
+    /// Returns methods of the `Route`s that match to given path, in the order they were added.
+    ///
+    /// This is what the `Allow` header of a 405 Method Not Allowed response contains.
+    /// HEAD is listed after GET, as it is served by GET routes, and OPTIONS is listed last,
+    /// as the router answers it for every known path. For the `*` path, which is used by
+    /// `OPTIONS *` requests, methods of all routes are returned.
+    ///
+    /// Unless the `TrailingSlash` policy is strict, a path without routes gets the methods
+    /// of its form with the trailing slash added or removed. Routes restricted to a host
+    /// are included regardless of their host.
+    pub fn allowed_methods(&self, request_path: &str) -> Vec<Method> {
+        self.methods_at(request_path, None)
+    }
+
+    fn methods_at(&self, request_path: &str, target: Option<&Target>) -> Vec<Method> {
+        let mut routes = match request_path {
+            "*" => self.all_routes(),
+            _ => self.matching_routes(request_path, target),
+        };
+        if routes.is_empty() && self.trailing_slash != TrailingSlash::Strict {
+            if let Some(toggled) = toggle_trailing_slash(request_path) {
+                routes = self.matching_routes(&toggled, target);
+            }
+        }
+        let mut methods: Vec<Method> = vec![];
+        for route in routes {
+            if !methods.contains(&route.method) {
+                methods.push(route.method.clone());
+            }
+            if route.method == Method::GET && !methods.contains(&Method::HEAD) {
+                methods.push(Method::HEAD);
+            }
+        }
+        if (request_path == "*" || !methods.is_empty()) && !methods.contains(&Method::OPTIONS) {
+            methods.push(Method::OPTIONS);
+        }
+        methods
+    }
+
+    /// Builds the URL of the route with given name, filling its path parameters with given values.
+    ///
+    /// ```no_run
+    /// use hyper::{Body, Request, Response};
+    /// use hyper_router::{Route, RouterBuilder};
+    ///
+    /// fn user_handler(_: Request<Body>) -> Response<Body> {
+    ///     Response::new(Body::empty())
+    /// }
+    ///
+    /// let router = RouterBuilder::new()
+    ///     .add(Route::get("/user/:id").name("user_detail").using(user_handler))
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(router.url_for("user_detail", &[("id", "42")]).unwrap(), "/user/42");
+    /// ```
+    ///
+    /// Routes of mounted routers are found as well, with the mount prefix prepended.
+    /// If several routes have the same name the first one is used, like when matching requests.
+    pub fn url_for(&self, name: &str, params: &[(&str, &str)]) -> Result<String, UrlError> {
+        if let Some(route) = self.routes.iter().find(|route| {
+            route
+                .name
+                .as_ref()
+                .is_some_and(|route_name| route_name == name)
+        }) {
+            return route.path.url(params);
+        }
+        for mount in &self.mounts {
+            match mount.router.url_for(name, params) {
+                Ok(url) => {
+                    let prefix = mount.prefix.url(params)?;
+                    return Ok(match url.as_str() {
+                        "/" if !prefix.is_empty() => prefix,
+                        _ => format!("{}{}", prefix, url),
+                    });
+                }
+                Err(UrlError::UnknownRoute(_)) => {}
+                Err(error) => return Err(error),
+            }
+        }
+        Err(UrlError::UnknownRoute(name.to_string()))
+    }
+
+    /// Returns duplicate and shadowed routes found when the router was built.
+    ///
+    /// These are `RouterError::DuplicateRoute` and `RouterError::ShadowedRoute` warnings;
+    /// with `RouterBuilder::strict` they fail the build instead. Routes of mounted routers
+    /// are reported by the mounted router.
+    pub fn conflicts(&self) -> &[RouterError] {
+        &self.conflicts
+    }
+
+    fn all_routes(&self) -> Vec<&Route> {
+        let mut routes: Vec<&Route> = self.routes.iter().collect();
+        for mount in &self.mounts {
+            routes.extend(mount.router.all_routes());
+        }
+        routes
+    }
+
+    /// Checks if the router should answer the request on its own as an OPTIONS request,
+    /// given the status of finding its route.
+    fn answers_options(&self, request: &Request<Body>, status_code: StatusCode) -> bool {
+        request.method() == Method::OPTIONS
+            && (status_code == StatusCode::METHOD_NOT_ALLOWED || request.uri().path() == "*")
+    }
+
+    fn find_route_at(
+        &self,
+        path: &str,
+        method: &Method,
+        target: Option<&Target>,
+    ) -> HttpResult<RouteMatch<'_>> {
+        let mut status = StatusCode::NOT_FOUND;
+        let mut found: Option<usize> = None;
+        self.tree.for_each_match(&self.routes, path, |index| {
+            if !accepts(&self.routes[index], target) {
+                return;
+            }
+            if self.routes[index].method == method {
+                found = Some(found.map_or(index, |found| found.min(index)));
+            } else {
+                status = StatusCode::METHOD_NOT_ALLOWED;
+            }
+        });
+        if let Some(index) = found {
+            let route = &self.routes[index];
+            let mut params = host_params(route.host.as_ref(), target);
+            for (name, value) in route.path.params(path).unwrap_or_default().iter() {
+                params.insert(name, value);
+            }
+            return Ok(RouteMatch {
+                route,
+                params,
+                mounts: vec![],
+            });
+        }
+        for mount in &self.mounts {
+            if !host_matches(mount.host.as_ref(), target) {
+                continue;
+            }
+            let (prefix_len, prefix_params) = match mount.prefix.prefix_match(path) {
+                Some(prefix_match) => prefix_match,
+                None => continue,
+            };
+            let mut params = host_params(mount.host.as_ref(), target);
+            for (name, value) in prefix_params.iter() {
+                params.insert(name, value);
+            }
+            match mount
+                .router
+                .find_route_at(mount::strip_prefix(path, prefix_len), method, target)
+            {
+                Ok(mut route_match) => {
+                    for (name, value) in route_match.params.iter() {
+                        params.insert(name, value);
+                    }
+                    route_match.params = params;
+                    route_match.mounts.insert(
+                        0,
+                        mount::MountMatch {
+                            router: &mount.router,
+                            prefix_len,
+                        },
+                    );
+                    return Ok(route_match);
+                }
+                Err(StatusCode::METHOD_NOT_ALLOWED) => status = StatusCode::METHOD_NOT_ALLOWED,
+                Err(_) => {}
+            }
+        }
+        Err(status)
+    }
+}
+
+/// Request a route is looked for, with its host found once a `Host` pattern is checked.
+struct Target<'a> {
+    request: &'a Request<Body>,
+    host: OnceCell<String>,
+}
+
+impl<'a> Target<'a> {
+    fn new(request: &'a Request<Body>) -> Target<'a> {
+        Target {
+            request,
+            host: OnceCell::new(),
+        }
+    }
+
+    fn host(&self) -> &str {
+        self.host.get_or_init(|| host::request_host(self.request))
+    }
+}
+
+/// Checks if the host and the guards of given route accept the request, `None` accepting
+/// any request.
+fn accepts(route: &Route, target: Option<&Target>) -> bool {
+    host_matches(route.host.as_ref(), target)
+        && target.is_none_or(|target| route.guards.iter().all(|guard| guard.check(target.request)))
+}
+
+/// Checks if a route or mount restricted to given host matches the host of a request,
+/// `None` matching any host.
+fn host_matches(pattern: Option<&Host>, target: Option<&Target>) -> bool {
+    match (pattern, target) {
+        (Some(pattern), Some(target)) => pattern.matches_request_host(target.host()),
+        _ => true,
+    }
+}
+
+/// Returns the parameters captured from the host of a request by given pattern.
+fn host_params(pattern: Option<&Host>, target: Option<&Target>) -> Params {
+    match (pattern, target) {
+        (Some(pattern), Some(target)) => pattern
+            .request_host_params(target.host())
+            .unwrap_or_default(),
+        _ => Params::new(),
+    }
+}
+
+/// Returns given path with its trailing slash added or removed, `None` for `/` and `*`.
+fn toggle_trailing_slash(path: &str) -> Option<String> {
+    match path {
+        "/" | "*" | "" => None,
+        _ => match path.strip_suffix('/') {
+            Some(trimmed) => Some(trimmed.to_string()),
+            None => Some(format!("{}/", path)),
+        },
+    }
+}
+
+fn is_redirect(status_code: StatusCode) -> bool {
+    status_code == StatusCode::MOVED_PERMANENTLY || status_code == StatusCode::PERMANENT_REDIRECT
 }
 
 /// The default simple router service.
@@ -171,13 +668,16 @@ impl RouterService {
     }
 
     fn default_error_handler(status_code: StatusCode) -> Response<Body> {
-        let error = "Routing error: page not found";
+        let error = match status_code {
+            StatusCode::NOT_FOUND => "Routing error: page not found",
+            StatusCode::METHOD_NOT_ALLOWED => "Routing error: method not allowed",
+            StatusCode::BAD_REQUEST => "Routing error: bad request",
+            StatusCode::MISDIRECTED_REQUEST => "Routing error: misdirected request",
+            _ => "Routing error",
+        };
         Response::builder()
             .header(CONTENT_LENGTH, error.len() as u64)
-            .status(match status_code {
-                StatusCode::NOT_FOUND => StatusCode::NOT_FOUND,
-                _ => StatusCode::INTERNAL_SERVER_ERROR,
-            })
+            .status(status_code)
             .body(Body::from(error))
             .expect("Failed to construct a response")
     }
@@ -187,12 +687,105 @@ impl Service for RouterService {
     type ReqBody = Body;
     type ResBody = Body;
     type Error = hyper::Error;
-    type Future = FutureResult<Response<Body>, hyper::Error>;
+    type Future = ResponseFuture;
 
     fn call(&mut self, request: Request<Self::ReqBody>) -> Self::Future {
-        futures::future::ok(match self.router.find_handler(&request) {
-            Ok(handler) => handler(request),
-            Err(status_code) => (self.error_handler)(status_code),
-        })
+        let cors = match &self.router.cors {
+            Some(cors) => cors.clone(),
+            None => return self.dispatch(request),
+        };
+        let origin = match cors.allowed_origin(&request) {
+            Some(origin) => origin,
+            None => return self.dispatch_varying(request, cors),
+        };
+        if cors.is_preflight(&request) {
+            let methods = self.router.request_methods(&request);
+            return match cors.preflight_response(&request, &origin, methods) {
+                Some(response) => Box::new(futures::future::ok(response)),
+                None => self.dispatch_varying(request, cors),
+            };
+        }
+        Box::new(self.dispatch(request).map(move |mut response| {
+            cors.add_headers(&origin, &mut response);
+            response
+        }))
+    }
+}
+
+impl RouterService {
+    /// Dispatches a request that gets no CORS headers, marking the response as depending
+    /// on the `Origin` header if other origins would get them.
+    fn dispatch_varying(&self, request: Request<Body>, cors: Arc<Cors>) -> ResponseFuture {
+        Box::new(self.dispatch(request).map(move |mut response| {
+            cors.add_vary(&mut response);
+            response
+        }))
+    }
+
+    /// Runs the request through the middleware chain and the handler of its route.
+    fn dispatch(&self, mut request: Request<Body>) -> ResponseFuture {
+        let target = Target::new(&request);
+        let (path, result) = match self.router.normalized_path(request.uri().path()) {
+            Ok(path) => {
+                let path = path.into_owned();
+                let result = self
+                    .router
+                    .find_route_in(&path, request.method(), Some(&target));
+                (path, result)
+            }
+            Err(status_code) => (request.uri().path().to_string(), Err(status_code)),
+        };
+        match result {
+            Ok(route_match) => {
+                let chain = middleware::chain_for(&self.router, &path, Some(&route_match));
+                self.router.states.insert_into(&mut request);
+                for mount in &route_match.mounts {
+                    mount.router.states.insert_into(&mut request);
+                }
+                let handler = match (request.method(), &route_match.route.method) {
+                    (&Method::HEAD, &Method::GET) => {
+                        handlers::without_body(route_match.route.handler.clone())
+                    }
+                    _ => route_match.route.handler.clone(),
+                };
+                request.extensions_mut().insert(route_match.params);
+                Next::new(chain, handler).run(request)
+            }
+            Err(status_code) => {
+                let chain = middleware::chain_for(&self.router, &path, None);
+                let error_handler = self.error_handler;
+                let answers_options = self.router.answers_options(&request, status_code);
+                let allowed_methods =
+                    if answers_options || status_code == StatusCode::METHOD_NOT_ALLOWED {
+                        self.router.methods_at(&path, Some(&target))
+                    } else {
+                        vec![]
+                    };
+                self.router.states.insert_into(&mut request);
+                if answers_options {
+                    let handler = handlers::options_handler(allowed_methods);
+                    return Next::new(chain, handler).run(request);
+                }
+                if is_redirect(status_code) {
+                    let location = self.router.trailing_slash_location(request.uri());
+                    let handler =
+                        handlers::redirect_handler(status_code, location.unwrap_or_default());
+                    return Next::new(chain, handler).run(request);
+                }
+                Next::new(
+                    chain,
+                    Arc::new(move |_: Request<Body>| {
+                        let mut response = error_handler(status_code);
+                        if !allowed_methods.is_empty() {
+                            response
+                                .headers_mut()
+                                .insert(ALLOW, handlers::allow_header(&allowed_methods));
+                        }
+                        response
+                    }),
+                )
+                .run(request)
+            }
+        }
     }
 }
@@ -0,0 +1,181 @@
+use hyper::{Body, Request};
+use std::fmt;
+use std::sync::Arc;
+
+use crate::mount;
+use crate::{Handler, IntoResponseFuture, Path, ResponseFuture, RouteMatch, Router};
+
+/// Code run around request handlers.
+///
+/// Middleware receives the request together with `Next`, the rest of the chain. It may
+/// inspect or modify the request before passing it on with `Next::run`, post-process
+/// the response future returned by it, or skip it entirely and respond on its own.
+///
+/// It is implemented for every function and closure with a matching signature:
+///
+/// ```no_run
+/// use futures::Future;
+/// use hyper::header::{HeaderValue, SERVER};
+/// use hyper::{Body, Request, Response, StatusCode};
+/// use hyper_router::{Next, ResponseFuture, RouterBuilder};
+///
+/// fn require_auth(request: Request<Body>, next: Next) -> ResponseFuture {
+///     if request.headers().contains_key("authorization") {
+///         next.run(request)
+///     } else {
+///         let mut response = Response::new(Body::empty());
+///         *response.status_mut() = StatusCode::UNAUTHORIZED;
+///         Box::new(futures::future::ok(response))
+///     }
+/// }
+///
+/// fn server_header(request: Request<Body>, next: Next) -> ResponseFuture {
+///     Box::new(next.run(request).map(|mut response| {
+///         response.headers_mut().insert(SERVER, HeaderValue::from_static("hyper-router"));
+///         response
+///     }))
+/// }
+///
+/// RouterBuilder::new().middleware(server_header).middleware(require_auth);
+/// ```
+pub trait Middleware: Send + Sync + 'static {
+    fn handle(&self, request: Request<Body>, next: Next) -> ResponseFuture;
+}
+
+impl<F, R> Middleware for F
+where
+    F: Fn(Request<Body>, Next) -> R + Send + Sync + 'static,
+    R: IntoResponseFuture,
+{
+    fn handle(&self, request: Request<Body>, next: Next) -> ResponseFuture {
+        self(request, next).into_response_future()
+    }
+}
+
+impl fmt::Debug for dyn Middleware {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Middleware")
+    }
+}
+
+/// Middleware registered on a router, optionally limited to paths starting with a prefix.
+#[derive(Debug)]
+pub(crate) struct ScopedMiddleware {
+    pub prefix: Option<Path>,
+    /// The prefix matching regardless of case, used for case-insensitive routes.
+    pub insensitive_prefix: Option<Path>,
+    pub middleware: Arc<dyn Middleware>,
+}
+
+impl ScopedMiddleware {
+    pub fn new(middleware: Arc<dyn Middleware>) -> ScopedMiddleware {
+        ScopedMiddleware {
+            prefix: None,
+            insensitive_prefix: None,
+            middleware,
+        }
+    }
+
+    pub fn at(prefix: &str, middleware: Arc<dyn Middleware>) -> ScopedMiddleware {
+        ScopedMiddleware {
+            prefix: Some(Path::prefix(prefix)),
+            insensitive_prefix: Some(Path::prefix(prefix).case_insensitive()),
+            middleware,
+        }
+    }
+
+    /// Checks if the middleware applies to given path, matching its prefix regardless
+    /// of case when the route of the request is case-insensitive.
+    fn applies_to(&self, path: &str, case_insensitive: bool) -> bool {
+        let prefix = if case_insensitive {
+            &self.insensitive_prefix
+        } else {
+            &self.prefix
+        };
+        prefix.as_ref().is_none_or(|prefix| prefix.is_match(path))
+    }
+}
+
+/// Collects the middleware to run for a request with given path: middleware of the router
+/// in the order it was added, followed by the middleware of the mounted routers leading
+/// to the matched route and the middleware of the route itself.
+///
+/// Prefixes are matched regardless of case when the matched route is case-insensitive,
+/// so middleware guarding a prefix cannot be bypassed by changing the case of the path.
+pub(crate) fn chain_for(
+    router: &Router,
+    path: &str,
+    route_match: Option<&RouteMatch>,
+) -> Arc<Vec<Arc<dyn Middleware>>> {
+    let case_insensitive =
+        route_match.is_some_and(|route_match| route_match.route.path.is_case_insensitive());
+    let mut chain = vec![];
+    extend_with_router_middleware(&mut chain, router, path, case_insensitive);
+    if let Some(route_match) = route_match {
+        let mut path = path;
+        for mount in &route_match.mounts {
+            path = mount::strip_prefix(path, mount.prefix_len);
+            chain.push(Arc::new(mount::enter_mount(path.to_string())));
+            extend_with_router_middleware(&mut chain, mount.router, path, case_insensitive);
+        }
+        chain.extend(route_match.route.middleware.iter().cloned());
+    }
+    Arc::new(chain)
+}
+
+fn extend_with_router_middleware(
+    chain: &mut Vec<Arc<dyn Middleware>>,
+    router: &Router,
+    path: &str,
+    case_insensitive: bool,
+) {
+    chain.extend(
+        router
+            .middleware
+            .iter()
+            .filter(|scoped| scoped.applies_to(path, case_insensitive))
+            .map(|scoped| scoped.middleware.clone()),
+    );
+}
+
+/// The rest of the middleware chain, ending with the request handler.
+#[derive(Clone)]
+pub struct Next {
+    chain: Arc<Vec<Arc<dyn Middleware>>>,
+    position: usize,
+    handler: Arc<dyn Handler>,
+}
+
+impl Next {
+    pub(crate) fn new(chain: Arc<Vec<Arc<dyn Middleware>>>, handler: Arc<dyn Handler>) -> Next {
+        Next {
+            chain,
+            position: 0,
+            handler,
+        }
+    }
+
+    /// Passes the request to the next middleware or, at the end of the chain, to the handler.
+    pub fn run(self, request: Request<Body>) -> ResponseFuture {
+        match self.chain.get(self.position).cloned() {
+            Some(middleware) => middleware.handle(
+                request,
+                Next {
+                    position: self.position + 1,
+                    ..self
+                },
+            ),
+            None => self.handler.handle(request),
+        }
+    }
+}
+
+impl fmt::Debug for Next {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Next {{remaining: {}}}",
+            self.chain.len() - self.position
+        )
+    }
+}
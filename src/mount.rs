@@ -0,0 +1,71 @@
+use hyper::http::uri::PathAndQuery;
+use hyper::{Body, Request, Uri};
+
+use crate::{normalize, Host, Next, Path, ResponseFuture, Router};
+
+/// `Router` mounted under a path prefix, or for a host, of another router.
+#[derive(Debug)]
+pub(crate) struct Mount {
+    pub prefix: Path,
+    pub host: Option<Host>,
+    pub router: Router,
+}
+
+/// Mounted router that handles a request, with the length of the prefix stripped for it.
+#[derive(Debug)]
+pub(crate) struct MountMatch<'a> {
+    pub router: &'a Router,
+    pub prefix_len: usize,
+}
+
+/// URI of the request before it was passed to a mounted router.
+///
+/// Routers mounted with `RouterBuilder::mount` see the request path with their prefix
+/// stripped. The original URI is stored in the request extensions:
+///
+/// ```no_run
+/// use hyper::{Body, Request, Response};
+/// use hyper_router::OriginalUri;
+///
+/// fn handler(request: Request<Body>) -> Response<Body> {
+///     let uri = match request.extensions().get::<OriginalUri>() {
+///         Some(original) => original.0.to_string(),
+///         None => request.uri().to_string(),
+///     };
+///     Response::new(Body::from(uri))
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct OriginalUri(pub Uri);
+
+/// Returns the path seen by a router mounted under a prefix of given length.
+pub(crate) fn strip_prefix(path: &str, prefix_len: usize) -> &str {
+    if prefix_len >= path.len() {
+        "/"
+    } else {
+        &path[prefix_len..]
+    }
+}
+
+/// Middleware entering a mounted router: replaces the request path with given one.
+pub(crate) fn enter_mount(path: String) -> impl Fn(Request<Body>, Next) -> ResponseFuture {
+    move |mut request: Request<Body>, next: Next| {
+        if request.extensions().get::<OriginalUri>().is_none() {
+            let original = OriginalUri(request.uri().clone());
+            request.extensions_mut().insert(original);
+        }
+        let mut parts = request.uri().clone().into_parts();
+        let path = normalize::encode_path(&path);
+        let path_and_query = match request.uri().query() {
+            Some(query) => format!("{}?{}", path, query),
+            None => path.into_owned(),
+        };
+        parts.path_and_query = Some(
+            path_and_query
+                .parse::<PathAndQuery>()
+                .expect("Stripped path should be valid"),
+        );
+        *request.uri_mut() = Uri::from_parts(parts).expect("Stripped URI should be valid");
+        next.run(request)
+    }
+}
@@ -0,0 +1,101 @@
+use hyper::StatusCode;
+use std::borrow::Cow;
+
+use crate::{HttpResult, Params, PathNormalization};
+
+/// Returns the path routes are matched against, according to given policy.
+///
+/// Segments are percent-decoded, except for `%2F` and `%25`, so that decoded slashes do not
+/// split segments and decoded `%` signs are not decoded again. Empty segments and dot
+/// segments are then removed or, with `PathNormalization::Reject`, rejected with 400.
+/// Invalid percent-encoding and encoded bytes that are not UTF-8 are rejected with 400 too.
+pub(crate) fn normalize(path: &str, policy: PathNormalization) -> HttpResult<Cow<'_, str>> {
+    if policy == PathNormalization::Raw || !path.starts_with('/') {
+        return Ok(Cow::Borrowed(path));
+    }
+    let raw_segments: Vec<&str> = path[1..].split('/').collect();
+    let last = raw_segments.len() - 1;
+    let mut segments: Vec<String> = vec![];
+    let mut trailing_slash = false;
+    for (index, raw) in raw_segments.into_iter().enumerate() {
+        let segment = decode_segment(raw)?;
+        let removed = match segment.as_str() {
+            "" if index == last => false,
+            "" | "." => true,
+            ".." => {
+                segments.pop();
+                true
+            }
+            _ => {
+                segments.push(segment);
+                trailing_slash = false;
+                continue;
+            }
+        };
+        if removed && policy == PathNormalization::Reject {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        trailing_slash = index == last;
+    }
+    let mut normalized = format!("/{}", segments.join("/"));
+    if trailing_slash && !segments.is_empty() {
+        normalized.push('/');
+    }
+    if normalized == path {
+        Ok(Cow::Borrowed(path))
+    } else {
+        Ok(Cow::Owned(normalized))
+    }
+}
+
+fn decode_segment(raw: &str) -> HttpResult<String> {
+    let bytes = raw.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] != b'%' {
+            decoded.push(bytes[index]);
+            index += 1;
+            continue;
+        }
+        let byte = bytes
+            .get(index + 1..index + 3)
+            .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            .ok_or(StatusCode::BAD_REQUEST)?;
+        match byte {
+            b'/' => decoded.extend_from_slice(b"%2F"),
+            b'%' => decoded.extend_from_slice(b"%25"),
+            _ => decoded.push(byte),
+        }
+        index += 3;
+    }
+    String::from_utf8(decoded).map_err(|_| StatusCode::BAD_REQUEST)
+}
+
+/// Decodes the `%2F` and `%25` sequences left in parameter values by `normalize`.
+pub(crate) fn decode_params(params: Params) -> Params {
+    let mut decoded = Params::new();
+    for (name, value) in params.iter() {
+        decoded.insert(name, &value.replace("%2F", "/").replace("%25", "%"));
+    }
+    decoded
+}
+
+/// Percent-encodes the characters of a normalized path that may not appear in a URI.
+pub(crate) fn encode_path(path: &str) -> Cow<'_, str> {
+    let allowed = |byte: u8| byte.is_ascii_graphic() && !b"\"#<>?[\\]^`{|}".contains(&byte);
+    if path.bytes().all(allowed) {
+        return Cow::Borrowed(path);
+    }
+    let mut encoded = String::new();
+    for byte in path.bytes() {
+        if allowed(byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    Cow::Owned(encoded)
+}
@@ -0,0 +1,118 @@
+use hyper::StatusCode;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Parameters captured from the request path.
+///
+/// When a request is dispatched by `RouterService` the parameters of the matched route
+/// are stored in the request extensions, so the handler can read them like this:
+///
+/// ```no_run
+/// use hyper::{Body, Request, Response};
+/// use hyper_router::Params;
+///
+/// fn person_handler(request: Request<Body>) -> Response<Body> {
+///     let id = request
+///         .extensions()
+///         .get::<Params>()
+///         .and_then(|params| params.get("id"))
+///         .unwrap_or("unknown")
+///         .to_string();
+///     Response::new(Body::from(id))
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Params {
+    values: Vec<(String, String)>,
+}
+
+impl Params {
+    pub fn new() -> Params {
+        Params { values: vec![] }
+    }
+
+    /// Returns the value of the parameter with given name.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the value of the parameter with given name converted to the requested type.
+    ///
+    /// Conversion errors convert to `StatusCode::BAD_REQUEST`, so they can be propagated
+    /// with `?` from handlers returning `HttpResult<Response<Body>>`, which respond with 400:
+    ///
+    /// ```no_run
+    /// use hyper::{Body, Request, Response};
+    /// use hyper_router::{HttpResult, Params};
+    ///
+    /// fn show_user(request: Request<Body>) -> HttpResult<Response<Body>> {
+    ///     let params = request.extensions().get::<Params>().unwrap();
+    ///     let id = params.parse::<u64>("id")?;
+    ///     Ok(Response::new(Body::from(format!("user {}", id))))
+    /// }
+    /// ```
+    pub fn parse<T: FromStr>(&self, name: &str) -> Result<T, ParamError> {
+        let value = self
+            .get(name)
+            .ok_or_else(|| ParamError::Missing(name.to_string()))?;
+        value.parse().map_err(|_| ParamError::Invalid {
+            name: name.to_string(),
+            value: value.to_string(),
+        })
+    }
+
+    /// Sets the value of the parameter with given name, replacing the previous one.
+    pub fn insert(&mut self, name: &str, value: &str) {
+        match self.values.iter_mut().find(|(key, _)| key == name) {
+            Some(entry) => entry.1 = value.to_string(),
+            None => self.values.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    /// Returns an iterator over parameter names and values in the order they appear in the path.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// Error returned by `Params::parse`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamError {
+    /// The parameter was not captured from the path.
+    Missing(String),
+    /// The parameter value could not be converted to the requested type.
+    Invalid { name: String, value: String },
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParamError::Missing(name) => write!(f, "missing path parameter `{}`", name),
+            ParamError::Invalid { name, value } => {
+                write!(f, "invalid value `{}` of path parameter `{}`", value, name)
+            }
+        }
+    }
+}
+
+impl Error for ParamError {}
+
+impl From<ParamError> for StatusCode {
+    fn from(_: ParamError) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+}
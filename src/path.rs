@@ -1,10 +1,19 @@
 extern crate regex;
 use self::regex::Regex;
 
+use crate::Params;
+use std::error::Error;
+use std::fmt;
+
 /// Represents a path in HTTP sense (starting from `/`)
 #[derive(Debug)]
 pub struct Path {
     pub matcher: Regex,
+    pattern: String,
+    constraints: Vec<(String, ParamType)>,
+    error: Option<PathError>,
+    end: &'static str,
+    case_insensitive: bool,
 }
 
 impl Path {
@@ -20,12 +29,797 @@ impl Path {
     ///
     /// Note that you don't have to match beggining and end of the
     /// path using `^` and `$` - those are inserted for you automatically.
+    ///
+    /// A path segment written as `:name` or `{name}` matches any single segment and captures
+    /// its value as a parameter called `name`. Named regex groups are captured as well:
+    ///
+    /// ```no_run
+    /// use hyper_router::Path;
+    /// Path::new("/person/:id/posts/{post_id}");
+    /// Path::new(r"/person/(?P<id>\d+)");
+    /// ```
+    ///
+    /// A parameter may also declare its type as `{name:type}`. The path then only matches
+    /// when the segment parses as that type. Supported types are `u8`, `u16`, `u32`, `u64`,
+    /// `usize`, `i8`, `i16`, `i32`, `i64`, `isize`, `f32`, `f64`, `bool`, `uuid`, `slug`
+    /// and `str`:
+    ///
+    /// ```no_run
+    /// use hyper_router::Path;
+    /// Path::new("/user/{id:u64}/files/{file:uuid}");
+    /// ```
+    ///
+    /// The last segment may be a wildcard written as `*name`, capturing the rest of the path
+    /// including slashes as `name`, or `**`, capturing it as `tail`. `/static/*file` matches
+    /// `/static/` and `/static/css/site.css`, but not `/static`:
+    ///
+    /// ```no_run
+    /// use hyper_router::Path;
+    /// Path::new("/static/*file");
+    /// Path::new("/files/**");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern is not a valid regular expression or uses an unknown parameter
+    /// type. Use `Path::try_new` to handle the error instead.
     pub fn new(path: &str) -> Path {
-        let mut regex = "^".to_string();
-        regex.push_str(path);
-        regex.push_str("$");
+        Path::try_new(path).unwrap_or_else(|error| panic!("Invalid path `{}`: {}", path, error))
+    }
+
+    /// Creates a new path like `Path::new`, returning an error for invalid patterns.
+    ///
+    /// ```no_run
+    /// use hyper_router::{Path, PathError};
+    /// assert_eq!(
+    ///     Path::try_new("/user/{id:int}").unwrap_err(),
+    ///     PathError::UnknownParamType("{id:int}".to_string())
+    /// );
+    /// ```
+    pub fn try_new(path: &str) -> Result<Path, PathError> {
+        Path::compile(path, "$")
+    }
+
+    /// Creates a path, keeping the error of an invalid pattern to be reported when the
+    /// router is built. Invalid paths do not match anything.
+    pub(crate) fn deferred(path: &str) -> Path {
+        Path::compile(path, "$").unwrap_or_else(|error| Path::invalid(path, error))
+    }
+
+    /// Creates a path matching the beginning of other paths, up to a `/` or the end of the path.
+    ///
+    /// Errors are deferred like with `Path::deferred`.
+    pub(crate) fn prefix(path: &str) -> Path {
+        let path = path.trim_end_matches('/');
+        Path::compile(path, "(?:/|$)").unwrap_or_else(|error| Path::invalid(path, error))
+    }
+
+    fn invalid(path: &str, error: PathError) -> Path {
         Path {
-            matcher: Regex::new(&regex).unwrap(),
+            matcher: Regex::new(r"^\b\B").unwrap(),
+            pattern: path.to_string(),
+            constraints: vec![],
+            error: Some(error),
+            end: "$",
+            case_insensitive: false,
+        }
+    }
+
+    fn compile(path: &str, end: &'static str) -> Result<Path, PathError> {
+        Path::compile_with_case(path, end, false)
+    }
+
+    fn compile_with_case(
+        path: &str,
+        end: &'static str,
+        case_insensitive: bool,
+    ) -> Result<Path, PathError> {
+        let mut regex = "^".to_string();
+        let mut constraints = vec![];
+        let mut segments = vec![];
+        let split = split_segments(path);
+        let last = split.len() - 1;
+        for (index, segment) in split.into_iter().enumerate() {
+            match parse_param(segment) {
+                Some((name, param_type)) => {
+                    let param_type = param_type
+                        .ok_or_else(|| PathError::UnknownParamType(segment.to_string()))?;
+                    if param_type == ParamType::Tail && index != last {
+                        return Err(PathError::WildcardNotLast(segment.to_string()));
+                    }
+                    if param_type.needs_validation() {
+                        constraints.push((name.to_string(), param_type));
+                    }
+                    segments.push(format!("(?P<{}>{})", name, param_type.regex()));
+                }
+                None if case_insensitive => segments.push(format!("(?i:{})", segment)),
+                None => segments.push(segment.to_string()),
+            }
+        }
+        regex.push_str(&segments.join("/"));
+        regex.push_str(end);
+        let matcher =
+            Regex::new(&regex).map_err(|error| PathError::InvalidRegex(error.to_string()))?;
+        Ok(Path {
+            matcher,
+            pattern: path.to_string(),
+            constraints,
+            error: None,
+            end,
+            case_insensitive,
+        })
+    }
+
+    /// Makes the path match static segments regardless of their case, so
+    /// `Path::new("/users/:name").case_insensitive()` matches `/Users/Ann` as well.
+    ///
+    /// Parameter values are captured as they appear in the request path. Segments written as
+    /// regular expressions are matched case-insensitively as a whole.
+    pub fn case_insensitive(mut self) -> Path {
+        self.make_case_insensitive();
+        self
+    }
+
+    pub(crate) fn is_case_insensitive(&self) -> bool {
+        self.case_insensitive
+    }
+
+    pub(crate) fn make_case_insensitive(&mut self) {
+        if self.case_insensitive {
+            return;
+        }
+        self.case_insensitive = true;
+        if self.error.is_none() {
+            if let Ok(path) = Path::compile_with_case(&self.pattern, self.end, true) {
+                *self = path;
+            }
+        }
+    }
+
+    /// Returns the error of a path created with an invalid pattern by a `Route` constructor.
+    pub(crate) fn error(&self) -> Option<&PathError> {
+        self.error.as_ref()
+    }
+
+    /// Returns the pattern this path was created from.
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Creates a new path by prepending given prefix to the pattern of this one.
+    ///
+    /// The prefix and the pattern are separated by a single `/`, and the pattern `/` becomes
+    /// the prefix itself, so `/` prefixed with `/api` matches `/api` but not `/api/`.
+    ///
+    /// If the result is not a valid pattern, it matches nothing and the error is reported
+    /// when the router is built.
+    pub fn with_prefix(&self, prefix: &str) -> Path {
+        let prefix = prefix.trim_end_matches('/');
+        let pattern = match self.pattern.as_str() {
+            "/" if !prefix.is_empty() => prefix.to_string(),
+            pattern => format!("{}/{}", prefix, pattern.trim_start_matches('/')),
+        };
+        let mut path = match &self.error {
+            Some(error) => Path::invalid(&pattern, error.clone()),
+            None => Path::deferred(&pattern),
+        };
+        if self.case_insensitive {
+            path.make_case_insensitive();
+        }
+        path
+    }
+
+    /// Checks if given path matches this one, including the types of its parameters.
+    pub fn is_match(&self, path: &str) -> bool {
+        if self.constraints.is_empty() {
+            self.matcher.is_match(path)
+        } else {
+            self.params(path).is_some()
+        }
+    }
+
+    /// Checks if the path has typed parameters validated after its regex matches.
+    pub(crate) fn has_constraints(&self) -> bool {
+        !self.constraints.is_empty()
+    }
+
+    /// Returns parameters captured from given path or `None` if the path does not match.
+    pub fn params(&self, path: &str) -> Option<Params> {
+        self.match_with_params(path).map(|(_, params)| params)
+    }
+
+    /// Matches a path created with `Path::prefix`, returning the length of the matched prefix
+    /// (without the trailing `/`) and the parameters captured from it.
+    pub(crate) fn prefix_match(&self, path: &str) -> Option<(usize, Params)> {
+        self.match_with_params(path).map(|(end, params)| {
+            if path[..end].ends_with('/') {
+                (end - 1, params)
+            } else {
+                (end, params)
+            }
+        })
+    }
+
+    /// Builds a path matching this one by filling its parameters with given values.
+    ///
+    /// Values are percent-encoded and have to match the type of their parameter:
+    ///
+    /// ```no_run
+    /// use hyper_router::Path;
+    /// let path = Path::new("/user/{id:u64}/posts/:slug");
+    /// assert_eq!(path.url(&[("id", "42"), ("slug", "hello world")]).unwrap(), "/user/42/posts/hello%20world");
+    /// ```
+    ///
+    /// Segments written as `:name`, `{name}`, `{name:type}` and named regex groups
+    /// are filled in, the rest of the path is copied. Patterns using regular expressions outside
+    /// of parameters cannot be reversed and return `UrlError::NotReversible`.
+    pub fn url(&self, params: &[(&str, &str)]) -> Result<String, UrlError> {
+        if self.error.is_some() {
+            return Err(UrlError::NotReversible(self.pattern.clone()));
+        }
+        let mut segments = vec![];
+        for segment in split_segments(&self.pattern) {
+            let parts =
+                url_parts(segment).ok_or_else(|| UrlError::NotReversible(self.pattern.clone()))?;
+            let mut url_segment = String::new();
+            for part in parts {
+                let (name, regex, param_type) = match part {
+                    UrlPart::Literal(literal) => {
+                        url_segment.push_str(&literal);
+                        continue;
+                    }
+                    UrlPart::Param(name, regex, param_type) => (name, regex, param_type),
+                };
+                let value = params
+                    .iter()
+                    .find(|(param, _)| *param == name)
+                    .map(|(_, value)| match param_type {
+                        Some(ParamType::Tail) => value
+                            .split('/')
+                            .map(encode_segment)
+                            .collect::<Vec<_>>()
+                            .join("/"),
+                        _ => encode_segment(value),
+                    })
+                    .ok_or_else(|| UrlError::MissingParam(name.to_string()))?;
+                let valid = full_match(regex, &value)
+                    && param_type.is_none_or(|param_type| param_type.accepts(&value));
+                if !valid {
+                    return Err(UrlError::InvalidParam {
+                        name: name.to_string(),
+                        value,
+                    });
+                }
+                url_segment.push_str(&value);
+            }
+            segments.push(url_segment);
+        }
+        Ok(segments.join("/"))
+    }
+
+    /// Returns the rank of each segment of the pattern: 0 for static text, 1 for typed
+    /// parameters, 2 for other parameters, 3 for regular expressions and 4 for wildcards.
+    ///
+    /// Comparing the ranks orders more specific paths first.
+    pub(crate) fn specificity(&self) -> Vec<u8> {
+        split_segments(&self.pattern)
+            .into_iter()
+            .map(|segment| match parse_param(segment) {
+                Some((_, Some(ParamType::Str))) => 2,
+                Some((_, Some(ParamType::Tail))) => 4,
+                Some((_, Some(_))) => 1,
+                Some((_, None)) => 3,
+                None => match url_parts(segment) {
+                    Some(parts) if parts.iter().all(|part| matches!(part, UrlPart::Literal(_))) => {
+                        0
+                    }
+                    _ => 3,
+                },
+            })
+            .collect()
+    }
+
+    /// Returns the segments of the pattern if all of them are static text or parameters,
+    /// so the path can be matched without running its regex.
+    pub(crate) fn plain_segments(&self) -> Option<Vec<PlainSegment>> {
+        if self.error.is_some() {
+            return None;
+        }
+        split_segments(&self.pattern)
+            .into_iter()
+            .map(|segment| match Shape::of(segment) {
+                Shape::Literal(literal) if !self.case_insensitive => {
+                    Some(PlainSegment::Static(literal))
+                }
+                Shape::Literal(literal) if literal.is_ascii() => {
+                    Some(PlainSegment::InsensitiveStatic(literal))
+                }
+                Shape::Literal(_) => None,
+                Shape::Param(param_type) => Some(PlainSegment::Param(param_type)),
+                Shape::Regex(_) => None,
+            })
+            .collect()
+    }
+
+    /// Checks if every path matched by `other` is matched by this path as well.
+    ///
+    /// The check is conservative: `false` is returned whenever it cannot be decided,
+    /// like for most regular expressions.
+    pub(crate) fn covers(&self, other: &Path) -> bool {
+        if self.error.is_some() || other.error.is_some() {
+            return false;
+        }
+        if other.case_insensitive && !self.case_insensitive {
+            return false;
+        }
+        if self.pattern == other.pattern {
+            return true;
+        }
+        let shapes: Vec<Shape> = split_segments(&self.pattern)
+            .into_iter()
+            .map(Shape::of)
+            .collect();
+        let other_shapes: Vec<Shape> = split_segments(&other.pattern)
+            .into_iter()
+            .map(Shape::of)
+            .collect();
+        let literal: Option<Vec<&str>> = other_shapes
+            .iter()
+            .map(|shape| match shape {
+                Shape::Literal(literal) => Some(literal.as_str()),
+                _ => None,
+            })
+            .collect();
+        match literal {
+            Some(literal) if !other.case_insensitive => return self.is_match(&literal.join("/")),
+            _ => {}
+        }
+        let ends_with_tail = matches!(shapes.last(), Some(Shape::Param(ParamType::Tail)));
+        let same_length = if ends_with_tail {
+            other_shapes.len() >= shapes.len()
+        } else {
+            other_shapes.len() == shapes.len()
+        };
+        same_length
+            && shapes
+                .iter()
+                .zip(&other_shapes)
+                .all(|(shape, other_shape)| {
+                    shape.covers(other_shape, self.case_insensitive, other.case_insensitive)
+                })
+    }
+
+    fn match_with_params(&self, path: &str) -> Option<(usize, Params)> {
+        let captures = self.matcher.captures(path)?;
+        let end = captures.get(0).map_or(0, |whole| whole.end());
+        let mut params = Params::new();
+        for name in self.matcher.capture_names().flatten() {
+            if let Some(value) = captures.name(name) {
+                params.insert(name, value.as_str());
+            }
+        }
+        let valid = self.constraints.iter().all(|(name, param_type)| {
+            params
+                .get(name)
+                .is_none_or(|value| param_type.accepts(value))
+        });
+        if valid {
+            Some((end, params))
+        } else {
+            None
+        }
+    }
+}
+
+/// Error returned for invalid path patterns.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathError {
+    /// The pattern is not a valid regular expression.
+    InvalidRegex(String),
+    /// A `{name:type}` segment uses a type that is not supported.
+    UnknownParamType(String),
+    /// A `*name` or `**` wildcard is not the last segment of the pattern.
+    WildcardNotLast(String),
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PathError::InvalidRegex(message) => {
+                write!(f, "invalid regular expression: {}", message)
+            }
+            PathError::UnknownParamType(segment) => {
+                write!(f, "unknown parameter type in `{}`", segment)
+            }
+            PathError::WildcardNotLast(segment) => {
+                write!(f, "wildcard `{}` is not the last segment", segment)
+            }
+        }
+    }
+}
+
+impl Error for PathError {}
+
+/// Error returned when building a URL with `Path::url` or `Router::url_for`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UrlError {
+    /// No route has the requested name.
+    UnknownRoute(String),
+    /// No value was given for the parameter.
+    MissingParam(String),
+    /// The parameter value does not match the parameter pattern or type.
+    InvalidParam { name: String, value: String },
+    /// The path uses regular expressions that cannot be filled in.
+    NotReversible(String),
+}
+
+impl fmt::Display for UrlError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UrlError::UnknownRoute(name) => write!(f, "no route named `{}`", name),
+            UrlError::MissingParam(name) => write!(f, "missing value of path parameter `{}`", name),
+            UrlError::InvalidParam { name, value } => {
+                write!(f, "invalid value `{}` of path parameter `{}`", value, name)
+            }
+            UrlError::NotReversible(pattern) => {
+                write!(f, "cannot build a URL from path `{}`", pattern)
+            }
+        }
+    }
+}
+
+impl Error for UrlError {}
+
+/// Type of a parameter declared as `{name:type}`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ParamType {
+    U8,
+    U16,
+    U32,
+    U64,
+    Usize,
+    I8,
+    I16,
+    I32,
+    I64,
+    Isize,
+    F32,
+    F64,
+    Bool,
+    Uuid,
+    Slug,
+    Str,
+    /// Rest of the path, written as `*name` or `**`.
+    Tail,
+}
+
+impl ParamType {
+    fn from_name(name: &str) -> Option<ParamType> {
+        match name {
+            "u8" => Some(ParamType::U8),
+            "u16" => Some(ParamType::U16),
+            "u32" => Some(ParamType::U32),
+            "u64" => Some(ParamType::U64),
+            "usize" => Some(ParamType::Usize),
+            "i8" => Some(ParamType::I8),
+            "i16" => Some(ParamType::I16),
+            "i32" => Some(ParamType::I32),
+            "i64" => Some(ParamType::I64),
+            "isize" => Some(ParamType::Isize),
+            "f32" => Some(ParamType::F32),
+            "f64" => Some(ParamType::F64),
+            "bool" => Some(ParamType::Bool),
+            "uuid" => Some(ParamType::Uuid),
+            "slug" => Some(ParamType::Slug),
+            "str" => Some(ParamType::Str),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn regex(self) -> &'static str {
+        match self {
+            ParamType::U8 | ParamType::U16 | ParamType::U32 | ParamType::U64 | ParamType::Usize => {
+                "[0-9]+"
+            }
+            ParamType::I8 | ParamType::I16 | ParamType::I32 | ParamType::I64 | ParamType::Isize => {
+                "-?[0-9]+"
+            }
+            ParamType::F32 | ParamType::F64 => r"-?[0-9]+(?:\.[0-9]+)?",
+            ParamType::Bool => "true|false",
+            ParamType::Uuid => {
+                "[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}"
+            }
+            ParamType::Slug => "[a-z0-9]+(?:-[a-z0-9]+)*",
+            ParamType::Str => "[^/]+",
+            ParamType::Tail => "(?s:.*)",
+        }
+    }
+
+    /// Integers may overflow even though they match the regex, so they are parsed after matching.
+    fn needs_validation(self) -> bool {
+        !matches!(
+            self,
+            ParamType::F32
+                | ParamType::F64
+                | ParamType::Bool
+                | ParamType::Uuid
+                | ParamType::Slug
+                | ParamType::Str
+                | ParamType::Tail
+        )
+    }
+
+    pub(crate) fn accepts(self, value: &str) -> bool {
+        match self {
+            ParamType::U8 => value.parse::<u8>().is_ok(),
+            ParamType::U16 => value.parse::<u16>().is_ok(),
+            ParamType::U32 => value.parse::<u32>().is_ok(),
+            ParamType::U64 => value.parse::<u64>().is_ok(),
+            ParamType::Usize => value.parse::<usize>().is_ok(),
+            ParamType::I8 => value.parse::<i8>().is_ok(),
+            ParamType::I16 => value.parse::<i16>().is_ok(),
+            ParamType::I32 => value.parse::<i32>().is_ok(),
+            ParamType::I64 => value.parse::<i64>().is_ok(),
+            ParamType::Isize => value.parse::<isize>().is_ok(),
+            _ => true,
+        }
+    }
+}
+
+/// Recognizes `:name`, `{name}`, `{name:type}`, `*name` and `**` segments.
+///
+/// Returns the parameter name and its type, or `None` as the type when it is unknown.
+fn parse_param(segment: &str) -> Option<(&str, Option<ParamType>)> {
+    if segment == "**" {
+        return Some(("tail", Some(ParamType::Tail)));
+    }
+    if let Some(name) = segment.strip_prefix('*') {
+        return if is_identifier(name) {
+            Some((name, Some(ParamType::Tail)))
+        } else {
+            None
+        };
+    }
+    if let Some(name) = segment.strip_prefix(':') {
+        return if is_identifier(name) {
+            Some((name, Some(ParamType::Str)))
+        } else {
+            None
+        };
+    }
+    let inner = segment.strip_prefix('{')?.strip_suffix('}')?;
+    let (name, param_type) = match inner.find(':') {
+        Some(index) => (&inner[..index], ParamType::from_name(&inner[index + 1..])),
+        None => (inner, Some(ParamType::Str)),
+    };
+    if is_identifier(name) {
+        Some((name, param_type))
+    } else {
+        None
+    }
+}
+
+/// Segment of a pattern returned by `Path::plain_segments`.
+#[derive(Debug)]
+pub(crate) enum PlainSegment {
+    Static(String),
+    /// Static text of a case-insensitive path, made only of ASCII characters.
+    InsensitiveStatic(String),
+    Param(ParamType),
+}
+
+/// Segment of a pattern as seen by `Path::covers`.
+enum Shape<'a> {
+    /// Segment matching only the given text.
+    Literal(String),
+    Param(ParamType),
+    Regex(&'a str),
+}
+
+impl<'a> Shape<'a> {
+    fn of(segment: &'a str) -> Shape<'a> {
+        match parse_param(segment) {
+            Some((_, Some(param_type))) => return Shape::Param(param_type),
+            Some((_, None)) => return Shape::Regex(segment),
+            None => {}
+        }
+        let mut literal = String::new();
+        let mut chars = segment.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some(escaped) if escaped.is_ascii_punctuation() => literal.push(escaped),
+                    _ => return Shape::Regex(segment),
+                },
+                '.' | '+' | '*' | '?' | '(' | ')' | '|' | '[' | ']' | '{' | '}' | '^' | '$' => {
+                    return Shape::Regex(segment)
+                }
+                _ => literal.push(c),
+            }
+        }
+        Shape::Literal(literal)
+    }
+
+    /// Checks if every text matched by `other` is matched by this segment, given whether
+    /// the literals of either segment match regardless of case.
+    fn covers(&self, other: &Shape, case_insensitive: bool, other_case_insensitive: bool) -> bool {
+        let catch_all = |regex: &str| matches!(regex, ".*" | ".+" | "[^/]+" | "[^/]*");
+        match (self, other) {
+            (Shape::Literal(literal), Shape::Literal(other)) if case_insensitive => {
+                full_match(&format!("(?i:{})", regex::escape(literal)), other)
+            }
+            (Shape::Literal(literal), Shape::Literal(other)) => literal == other,
+            (Shape::Param(param_type), Shape::Literal(_))
+                if other_case_insensitive && *param_type != ParamType::Str =>
+            {
+                false
+            }
+            (Shape::Param(param_type), Shape::Literal(other)) => {
+                full_match(param_type.regex(), other) && param_type.accepts(other)
+            }
+            (Shape::Regex(regex), Shape::Literal(other)) if case_insensitive => {
+                full_match(&format!("(?i:{})", regex), other)
+            }
+            (Shape::Regex(regex), Shape::Literal(other)) => full_match(regex, other),
+            (Shape::Param(ParamType::Tail), _) => true,
+            (_, Shape::Param(ParamType::Tail)) => matches!(self, Shape::Regex(".*")),
+            (Shape::Param(ParamType::Str), Shape::Param(_)) => true,
+            (Shape::Param(param_type), Shape::Param(other)) => param_type == other,
+            (Shape::Regex(regex), Shape::Param(_)) => catch_all(regex),
+            (Shape::Regex(regex), Shape::Regex(other)) => *regex == ".*" || regex == other,
+            _ => false,
+        }
+    }
+}
+
+fn full_match(regex: &str, text: &str) -> bool {
+    Regex::new(&format!("^(?:{})$", regex))
+        .map(|regex| regex.is_match(text))
+        .unwrap_or(false)
+}
+
+/// Part of a path segment when building a URL.
+enum UrlPart<'a> {
+    Literal(String),
+    Param(&'a str, &'a str, Option<ParamType>),
+}
+
+/// Splits a segment into literal text and parameters, given as name, regex and type.
+///
+/// Returns `None` if the segment uses regex syntax other than named groups and escaped
+/// punctuation. An unescaped `.` is taken literally, as in `/robots.txt`.
+fn url_parts(segment: &str) -> Option<Vec<UrlPart<'_>>> {
+    if let Some((name, param_type)) = parse_param(segment) {
+        let param_type = param_type?;
+        return Some(vec![UrlPart::Param(
+            name,
+            param_type.regex(),
+            Some(param_type),
+        )]);
+    }
+    let mut parts = vec![];
+    let mut literal = String::new();
+    let mut rest = segment;
+    while let Some(c) = rest.chars().next() {
+        if let Some(group) = rest.strip_prefix("(?P<") {
+            let name_end = group.find('>')?;
+            let regex_end = group_end(&group[name_end + 1..])?;
+            let name = &group[..name_end];
+            if !is_identifier(name) {
+                return None;
+            }
+            if !literal.is_empty() {
+                parts.push(UrlPart::Literal(literal.split_off(0)));
+            }
+            let regex = &group[name_end + 1..name_end + 1 + regex_end];
+            parts.push(UrlPart::Param(name, regex, None));
+            rest = &group[name_end + regex_end + 2..];
+            continue;
+        }
+        match c {
+            '\\' => match rest[1..].chars().next() {
+                Some(escaped) if escaped.is_ascii_punctuation() => {
+                    literal.push(escaped);
+                    rest = &rest[1 + escaped.len_utf8()..];
+                    continue;
+                }
+                _ => return None,
+            },
+            '+' | '*' | '?' | '(' | ')' | '|' | '[' | ']' | '{' | '}' | '^' | '$' => return None,
+            _ => literal.push(c),
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    if !literal.is_empty() || parts.is_empty() {
+        parts.push(UrlPart::Literal(literal));
+    }
+    Some(parts)
+}
+
+/// Returns the index of the `)` closing a group whose contents start the given text.
+fn group_end(regex: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut escaped = false;
+    for (index, c) in regex.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '(' => depth += 1,
+            ')' if depth == 0 => return Some(index),
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Percent-encodes characters that may not appear in a path segment.
+fn encode_segment(value: &str) -> String {
+    let mut encoded = String::new();
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'.'
+            | b'_'
+            | b'~'
+            | b'!'
+            | b'$'
+            | b'&'
+            | b'\''
+            | b'('
+            | b')'
+            | b'*'
+            | b'+'
+            | b','
+            | b';'
+            | b'='
+            | b':'
+            | b'@' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+pub(crate) fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        _ => false,
+    }
+}
+
+/// Splits the path on `/` characters that are not part of a regex group, character class
+/// or escape sequence.
+fn split_segments(path: &str) -> Vec<&str> {
+    let mut segments = vec![];
+    let mut start = 0;
+    let mut depth = 0;
+    let mut in_class = false;
+    let mut escaped = false;
+    for (index, c) in path.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' => escaped = true,
+            '[' if !in_class => in_class = true,
+            ']' if in_class => in_class = false,
+            '(' if !in_class => depth += 1,
+            ')' if !in_class && depth > 0 => depth -= 1,
+            '/' if !in_class && depth == 0 => {
+                segments.push(&path[start..index]);
+                start = index + 1;
+            }
+            _ => {}
         }
     }
+    segments.push(&path[start..]);
+    segments
 }
@@ -1,5 +1,9 @@
+use crate::Guard;
 use crate::Handler;
+use crate::Host;
+use crate::Middleware;
 use crate::Route;
+use std::sync::Arc;
 
 pub struct RouteBuilder {
     route: Route,
@@ -10,10 +14,83 @@ impl RouteBuilder {
         RouteBuilder { route }
     }
 
+    /// Adds `Middleware` run only for this route.
+    ///
+    /// It runs after the middleware of the router, in the order it was added.
+    pub fn middleware<M: Middleware>(mut self, middleware: M) -> RouteBuilder {
+        self.route.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Names the route, so its URL can be built with `Router::url_for`.
+    pub fn name(mut self, name: &str) -> RouteBuilder {
+        self.route.name = Some(name.to_string());
+        self
+    }
+
+    /// Makes the static segments of the route path match regardless of case,
+    /// like `Path::case_insensitive`.
+    pub fn case_insensitive(mut self) -> RouteBuilder {
+        self.route.path.make_case_insensitive();
+        self
+    }
+
+    /// Restricts the route to requests for given host, like `api.example.com`
+    /// or `{tenant}.example.com`. Parameters captured from the host are added to `Params`.
+    ///
+    /// See `Host` for the syntax of the pattern.
+    pub fn host(mut self, pattern: &str) -> RouteBuilder {
+        self.route.host = Some(Host::new(pattern));
+        self
+    }
+
+    /// Adds a `Guard` the request has to pass for this route to be chosen.
+    ///
+    /// Routes with several guards are chosen only when all of them pass.
+    pub fn guard(mut self, guard: Guard) -> RouteBuilder {
+        self.route.guards.push(guard);
+        self
+    }
+
     /// Completes the building process by taking the handler to process the request.
     ///
+    /// The handler may be a function or a closure returning either a `Response` or
+    /// a `ResponseFuture`. The latter is useful for handlers that need to wait for I/O,
+    /// like reading the request body:
+    ///
+    /// ```no_run
+    /// use futures::{Future, Stream};
+    /// use hyper::{Body, Request, Response};
+    /// use hyper_router::{ResponseFuture, Route};
+    ///
+    /// fn echo_handler(request: Request<Body>) -> ResponseFuture {
+    ///     Box::new(request.into_body().concat2().map(|body| Response::new(Body::from(body))))
+    /// }
+    ///
+    /// Route::post("/echo").using(echo_handler);
+    /// ```
+    ///
     /// Returns created route.
-    pub fn using(mut self, handler: Handler) -> Route {
+    pub fn using<H: Handler>(mut self, handler: H) -> Route {
+        self.route.handler = Arc::new(handler);
+        self.route
+    }
+
+    /// Completes the building process like `using`, with a handler already wrapped in `Arc`.
+    ///
+    /// This lets several routes share one handler, including one returned by
+    /// `Router::find_handler`:
+    ///
+    /// ```no_run
+    /// use hyper::{Body, Request, Response};
+    /// use hyper_router::{Handler, Route};
+    /// use std::sync::Arc;
+    ///
+    /// let handler: Arc<dyn Handler> = Arc::new(|_: Request<Body>| Response::new(Body::empty()));
+    /// Route::get("/").using_arc(handler.clone());
+    /// Route::get("/index").using_arc(handler);
+    /// ```
+    pub fn using_arc(mut self, handler: Arc<dyn Handler>) -> Route {
         self.route.handler = handler;
         self.route
     }
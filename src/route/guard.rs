@@ -0,0 +1,142 @@
+use hyper::{Body, Request};
+use regex::Regex;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::PathError;
+
+/// Extra condition a request has to meet to be handled by a `Route`, next to its method
+/// and path.
+///
+/// A route with guards is only chosen when all of them pass, otherwise the router looks
+/// for another route as if the path of the guarded one did not match:
+///
+/// ```no_run
+/// use hyper::{Body, Request, Response};
+/// use hyper_router::{Guard, Route};
+///
+/// fn handler(_: Request<Body>) -> Response<Body> {
+///     Response::new(Body::empty())
+/// }
+///
+/// Route::get("/items").guard(Guard::header_eq("accept-version", "2")).using(handler);
+/// Route::post("/hooks").guard(Guard::header("x-hub-signature")).using(handler);
+/// Route::get("/search").guard(Guard::query("q")).using(handler);
+/// ```
+#[derive(Clone)]
+pub struct Guard {
+    kind: GuardKind,
+}
+
+type GuardFn = Arc<dyn Fn(&Request<Body>) -> bool + Send + Sync>;
+
+#[derive(Clone)]
+enum GuardKind {
+    Header(String),
+    HeaderEq(String, String),
+    HeaderMatches(String, Regex),
+    InvalidHeaderPattern(String, String, PathError),
+    Query(String),
+    Custom(GuardFn),
+}
+
+impl Guard {
+    /// Passes when the request has a header with given name.
+    pub fn header(name: &str) -> Guard {
+        Guard {
+            kind: GuardKind::Header(name.to_string()),
+        }
+    }
+
+    /// Passes when a header with given name has given value.
+    pub fn header_eq(name: &str, value: &str) -> Guard {
+        Guard {
+            kind: GuardKind::HeaderEq(name.to_string(), value.to_string()),
+        }
+    }
+
+    /// Passes when the whole value of a header with given name matches given regular
+    /// expression.
+    ///
+    /// An invalid regular expression does not panic here - it is reported by
+    /// `RouterBuilder::build`.
+    pub fn header_matches(name: &str, pattern: &str) -> Guard {
+        let kind = match Regex::new(&format!("^(?:{})$", pattern)) {
+            Ok(regex) => GuardKind::HeaderMatches(name.to_string(), regex),
+            Err(error) => GuardKind::InvalidHeaderPattern(
+                name.to_string(),
+                pattern.to_string(),
+                PathError::InvalidRegex(error.to_string()),
+            ),
+        };
+        Guard { kind }
+    }
+
+    /// Passes when the query string has a parameter with given name, with or without a value.
+    pub fn query(name: &str) -> Guard {
+        Guard {
+            kind: GuardKind::Query(name.to_string()),
+        }
+    }
+
+    /// Passes when given function returns `true` for the request.
+    pub fn custom<F>(f: F) -> Guard
+    where
+        F: Fn(&Request<Body>) -> bool + Send + Sync + 'static,
+    {
+        Guard {
+            kind: GuardKind::Custom(Arc::new(f)),
+        }
+    }
+
+    /// Checks if given request passes the guard.
+    pub fn check(&self, request: &Request<Body>) -> bool {
+        let headers = request.headers();
+        match &self.kind {
+            GuardKind::Header(name) => headers.contains_key(name.as_str()),
+            GuardKind::HeaderEq(name, value) => headers
+                .get_all(name.as_str())
+                .iter()
+                .any(|header| header.as_bytes() == value.as_bytes()),
+            GuardKind::HeaderMatches(name, regex) => headers
+                .get_all(name.as_str())
+                .iter()
+                .filter_map(|header| header.to_str().ok())
+                .any(|header| regex.is_match(header)),
+            GuardKind::InvalidHeaderPattern(..) => false,
+            GuardKind::Query(name) => request.uri().query().is_some_and(|query| {
+                query
+                    .split('&')
+                    .any(|pair| pair.split('=').next() == Some(name.as_str()))
+            }),
+            GuardKind::Custom(f) => f(request),
+        }
+    }
+
+    /// Returns the pattern and the error of a guard created with an invalid regular expression.
+    pub(crate) fn error(&self) -> Option<(&str, &PathError)> {
+        match &self.kind {
+            GuardKind::InvalidHeaderPattern(_, pattern, error) => Some((pattern, error)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Debug for Guard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            GuardKind::Header(name) => write!(f, "Guard::header({:?})", name),
+            GuardKind::HeaderEq(name, value) => {
+                write!(f, "Guard::header_eq({:?}, {:?})", name, value)
+            }
+            GuardKind::HeaderMatches(name, regex) => {
+                write!(f, "Guard::header_matches({:?}, {:?})", name, regex.as_str())
+            }
+            GuardKind::InvalidHeaderPattern(name, pattern, _) => {
+                write!(f, "Guard::header_matches({:?}, {:?})", name, pattern)
+            }
+            GuardKind::Query(name) => write!(f, "Guard::query({:?})", name),
+            GuardKind::Custom(_) => write!(f, "Guard::custom(..)"),
+        }
+    }
+}
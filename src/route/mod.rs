@@ -1,5 +1,7 @@
 mod builder;
+mod guard;
 mod route_impl;
 
 pub use builder::RouteBuilder;
+pub use guard::Guard;
 pub use route_impl::Route;
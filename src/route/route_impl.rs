@@ -1,10 +1,15 @@
 use crate::handlers;
 use hyper::Method;
 use std::fmt;
+use std::sync::Arc;
 
 use super::RouteBuilder;
+use crate::Guard;
 use crate::Handler;
+use crate::Host;
+use crate::Middleware;
 use crate::Path;
+use crate::RouterError;
 
 /// Holds route information
 pub struct Route {
@@ -16,7 +21,8 @@ pub struct Route {
 
     /// Request handler
     ///
-    /// This should be method that accepts Hyper's Request and Response:
+    /// This should be method or closure that accepts Hyper's Request and returns Response
+    /// (or a future of it, see `Handler`):
     ///
     /// ```ignore
     /// use hyper::server::{Request, Response};
@@ -30,7 +36,19 @@ pub struct Route {
     ///         .with_body(body)
     /// }
     /// ```
-    pub handler: Handler,
+    pub handler: Arc<dyn Handler>,
+
+    /// Middleware run only for this route, after the middleware of the router
+    pub middleware: Vec<Arc<dyn Middleware>>,
+
+    /// Name used to build URLs of this route with `Router::url_for`
+    pub name: Option<String>,
+
+    /// Host to match, any host if not set
+    pub host: Option<Host>,
+
+    /// Extra conditions the request has to meet
+    pub guards: Vec<Guard>,
 }
 
 impl Route {
@@ -70,13 +88,33 @@ impl Route {
         Route::from(Method::PATCH, path)
     }
 
+    /// Creates a route for given method and path.
+    ///
+    /// An invalid path pattern does not panic here - it is reported by `RouterBuilder::build`.
     pub fn from(method: Method, path: &str) -> RouteBuilder {
         RouteBuilder::new(Route {
             method,
-            path: Path::new(path),
+            path: Path::deferred(path),
             ..Route::default()
         })
     }
+
+    /// Creates a route for given method and path, returning an error for invalid path patterns.
+    pub fn try_from(method: Method, path: &str) -> Result<RouteBuilder, RouterError> {
+        match Path::try_new(path) {
+            Ok(path) => Ok(RouteBuilder::new(Route {
+                method,
+                path,
+                ..Route::default()
+            })),
+            Err(error) => Err(RouterError::InvalidPath {
+                route: None,
+                method: Some(method),
+                pattern: path.to_string(),
+                error,
+            }),
+        }
+    }
 }
 
 impl Default for Route {
@@ -84,7 +122,11 @@ impl Default for Route {
         Route {
             method: Method::GET,
             path: Path::new("/"),
-            handler: handlers::not_implemented_handler,
+            handler: Arc::new(handlers::not_implemented_handler),
+            middleware: vec![],
+            name: None,
+            host: None,
+            guards: vec![],
         }
     }
 }
@@ -93,8 +135,12 @@ impl fmt::Debug for Route {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "Route {{method: {:?}, path: {:?}}}",
-            self.method, self.path
+            "Route {{method: {:?}, path: {:?}, name: {:?}, host: {:?}, guards: {:?}}}",
+            self.method,
+            self.path,
+            self.name,
+            self.host.as_ref().map(Host::pattern),
+            self.guards
         )
     }
 }
@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use crate::middleware::ScopedMiddleware;
+use crate::Middleware;
+use crate::Route;
+
+/// Group of routes sharing a path prefix.
+///
+/// Created by `RouterBuilder::scope`, which prepends the prefix to the path of every route
+/// added to the scope:
+///
+/// ```ignore
+/// RouterBuilder::new()
+///     .scope("/api/v1", |api| {
+///         api.add(Route::get("/users").using(list_users))
+///             .scope("/admin", |admin| admin.middleware(require_auth).add(Route::get("/stats").using(stats)))
+///     });
+/// ```
+///
+/// The routes above match `/api/v1/users` and `/api/v1/admin/stats`. Prefixes and paths are
+/// joined with a single `/`, and a route with the path `/` matches the prefix itself, `/api/v1`.
+/// Unlike the root route of a mounted router, it does not match `/api/v1/` unless the
+/// `TrailingSlash` policy allows it.
+#[derive(Debug)]
+pub struct Scope {
+    prefix: String,
+    pub(crate) routes: Vec<Route>,
+    pub(crate) middleware: Vec<ScopedMiddleware>,
+}
+
+impl Scope {
+    pub(crate) fn new(prefix: &str) -> Scope {
+        let prefix = prefix.trim_matches('/');
+        Scope {
+            prefix: match prefix {
+                "" => String::new(),
+                prefix => format!("/{}", prefix),
+            },
+            routes: vec![],
+            middleware: vec![],
+        }
+    }
+
+    /// Adds new `Route`, prepending the prefix of this scope to its path.
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(mut self, mut route: Route) -> Scope {
+        route.path = route.path.with_prefix(&self.prefix);
+        self.routes.push(route);
+        self
+    }
+
+    /// Adds a nested scope, whose prefix is appended to the prefix of this one.
+    pub fn scope<F>(mut self, prefix: &str, build: F) -> Scope
+    where
+        F: FnOnce(Scope) -> Scope,
+    {
+        let nested = build(Scope::new(&format!(
+            "{}/{}",
+            self.prefix,
+            prefix.trim_start_matches('/')
+        )));
+        self.routes.extend(nested.routes);
+        self.middleware.extend(nested.middleware);
+        self
+    }
+
+    /// Adds `Middleware` wrapping requests whose path starts with the prefix of this scope.
+    ///
+    /// It behaves like middleware added with `RouterBuilder::middleware_at`.
+    pub fn middleware<M: Middleware>(mut self, middleware: M) -> Scope {
+        self.middleware
+            .push(ScopedMiddleware::at(&self.prefix, Arc::new(middleware)));
+        self
+    }
+}
@@ -0,0 +1,79 @@
+use hyper::{Body, Request};
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// Application state shared by all handlers of a `Router`.
+///
+/// State added with `RouterBuilder::with_state` is stored in the extensions of every
+/// request dispatched by `RouterService`:
+///
+/// ```no_run
+/// use hyper::{Body, Request, Response};
+/// use hyper_router::State;
+///
+/// struct Config {
+///     greeting: String,
+/// }
+///
+/// fn greet_handler(request: Request<Body>) -> Response<Body> {
+///     let config = request.extensions().get::<State<Config>>().unwrap();
+///     Response::new(Body::from(config.greeting.clone()))
+/// }
+/// ```
+pub struct State<T>(Arc<T>);
+
+impl<T> State<T> {
+    pub fn new(value: T) -> State<T> {
+        State(Arc::new(value))
+    }
+}
+
+impl<T> Clone for State<T> {
+    fn clone(&self) -> State<T> {
+        State(self.0.clone())
+    }
+}
+
+impl<T> Deref for State<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for State<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "State({:?})", self.0)
+    }
+}
+
+type Inserter = Box<dyn Fn(&mut Request<Body>) + Send + Sync>;
+
+/// States of a `Router`, stored without their types.
+#[derive(Default)]
+pub(crate) struct States {
+    inserters: Vec<Inserter>,
+}
+
+impl States {
+    pub fn add<T: Send + Sync + 'static>(&mut self, state: State<T>) {
+        self.inserters.push(Box::new(move |request| {
+            request.extensions_mut().insert(state.clone());
+        }));
+    }
+
+    /// Stores all states in the extensions of given request.
+    pub fn insert_into(&self, request: &mut Request<Body>) {
+        for insert in &self.inserters {
+            insert(request);
+        }
+    }
+}
+
+impl fmt::Debug for States {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "States {{count: {}}}", self.inserters.len())
+    }
+}
@@ -0,0 +1,190 @@
+use regex::bytes::RegexSet;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::path::{ParamType, PlainSegment};
+use crate::Route;
+
+/// Prefix tree of the routes of a `Router`, matching paths one segment at a time.
+///
+/// Routes made only of static segments and parameters are stored in the tree, so finding
+/// them takes time proportional to the length of the path rather than the number of routes.
+/// Routes using other regular expressions are kept aside and matched in a single pass
+/// with a `RegexSet` of their regexes. The set is compiled without Unicode support,
+/// which keeps it small and fast, so it is only used for ASCII paths - like every path
+/// of a valid URI. Other paths are matched with the regex of each route.
+#[derive(Debug, Default)]
+pub(crate) struct RouteTree {
+    root: Node,
+    fallback: Vec<usize>,
+    fallback_set: Option<RegexSet>,
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    statics: HashMap<String, Node>,
+    /// Static segments of case-insensitive routes, by their lowercase text.
+    insensitive: HashMap<String, Node>,
+    params: Vec<ParamEdge>,
+    /// Indices of the routes whose pattern ends in this node.
+    routes: Vec<usize>,
+    /// Indices of the routes whose pattern ends with a wildcard after this node.
+    tails: Vec<usize>,
+}
+
+#[derive(Debug)]
+struct ParamEdge {
+    param_type: ParamType,
+    /// Matcher of the whole segment, `None` for parameters matching any non-empty segment.
+    matcher: Option<Regex>,
+    node: Node,
+}
+
+impl ParamEdge {
+    fn matches(&self, segment: &str) -> bool {
+        match &self.matcher {
+            Some(matcher) => matcher.is_match(segment) && self.param_type.accepts(segment),
+            None => !segment.is_empty(),
+        }
+    }
+}
+
+impl RouteTree {
+    pub fn new(routes: &[Route]) -> RouteTree {
+        let mut tree = RouteTree::default();
+        for (index, route) in routes.iter().enumerate() {
+            match route.path.plain_segments() {
+                Some(segments) => tree.root.insert(segments, index),
+                None => tree.fallback.push(index),
+            }
+        }
+        if !tree.fallback.is_empty() {
+            let regexes = tree
+                .fallback
+                .iter()
+                .map(|index| format!("(?-u){}", routes[*index].path.matcher.as_str()));
+            tree.fallback_set = RegexSet::new(regexes).ok();
+        }
+        tree
+    }
+
+    /// Returns the indices of the routes matching given path, in ascending order.
+    pub fn matching(&self, routes: &[Route], path: &str) -> Vec<usize> {
+        let mut indices = vec![];
+        self.for_each_match(routes, path, |index| indices.push(index));
+        indices.sort_unstable();
+        indices
+    }
+
+    /// Calls given function with the index of every route matching given path,
+    /// in no particular order.
+    pub fn for_each_match<F: FnMut(usize)>(&self, routes: &[Route], path: &str, mut f: F) {
+        self.root.collect(Some(path), &mut f);
+        let set = match &self.fallback_set {
+            Some(set) if path.is_ascii() => set,
+            _ => {
+                // The set could not be compiled, e.g. because it is too big, or cannot match the path.
+                self.fallback
+                    .iter()
+                    .filter(|index| routes[**index].path.is_match(path))
+                    .for_each(|index| f(*index));
+                return;
+            }
+        };
+        for position in set.matches(path.as_bytes()).iter() {
+            let route_path = &routes[self.fallback[position]].path;
+            if !route_path.has_constraints() || route_path.is_match(path) {
+                f(self.fallback[position]);
+            }
+        }
+    }
+}
+
+impl Node {
+    fn insert(&mut self, segments: Vec<PlainSegment>, index: usize) {
+        let mut node = self;
+        for segment in segments {
+            node = match segment {
+                PlainSegment::Static(text) => node.statics.entry(text).or_default(),
+                PlainSegment::InsensitiveStatic(text) => node
+                    .insensitive
+                    .entry(text.to_ascii_lowercase())
+                    .or_default(),
+                PlainSegment::Param(ParamType::Tail) => {
+                    node.tails.push(index);
+                    return;
+                }
+                PlainSegment::Param(param_type) => {
+                    let position = match node
+                        .params
+                        .iter()
+                        .position(|edge| edge.param_type == param_type)
+                    {
+                        Some(position) => position,
+                        None => {
+                            node.params.push(ParamEdge {
+                                param_type,
+                                matcher: match param_type {
+                                    ParamType::Str => None,
+                                    _ => Some(
+                                        Regex::new(&format!("^(?:{})$", param_type.regex()))
+                                            .unwrap(),
+                                    ),
+                                },
+                                node: Node::default(),
+                            });
+                            node.params.len() - 1
+                        }
+                    };
+                    &mut node.params[position].node
+                }
+            };
+        }
+        node.routes.push(index);
+    }
+
+    /// Visits routes matching the rest of the path, `None` once the whole path is consumed.
+    fn collect<F: FnMut(usize)>(&self, rest: Option<&str>, f: &mut F) {
+        let rest = match rest {
+            Some(rest) => rest,
+            None => {
+                self.routes.iter().for_each(|index| f(*index));
+                return;
+            }
+        };
+        self.tails.iter().for_each(|index| f(*index));
+        let (segment, rest) = match rest.find('/') {
+            Some(end) => (&rest[..end], Some(&rest[end + 1..])),
+            None => (rest, None),
+        };
+        if let Some(node) = self.statics.get(segment) {
+            node.collect(rest, f);
+        }
+        if !self.insensitive.is_empty() {
+            if let Some(node) = fold_case(segment).and_then(|key| self.insensitive.get(&key)) {
+                node.collect(rest, f);
+            }
+        }
+        for edge in &self.params {
+            if edge.matches(segment) {
+                edge.node.collect(rest, f);
+            }
+        }
+    }
+}
+
+/// Returns the key of given segment among case-insensitive static segments: its ASCII
+/// lowercase form, or `None` if it cannot match ASCII text regardless of case.
+///
+/// The Kelvin sign and the long s are folded too, as the regexes of the routes do.
+fn fold_case(segment: &str) -> Option<String> {
+    segment
+        .chars()
+        .map(|c| match c {
+            '\u{212A}' => Some('k'),
+            '\u{17F}' => Some('s'),
+            c if c.is_ascii() => Some(c.to_ascii_lowercase()),
+            _ => None,
+        })
+        .collect()
+}
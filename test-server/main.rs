@@ -20,7 +20,8 @@ fn router_service() -> Result<RouterService, std::io::Error> {
     let router = RouterBuilder::new()
         .add(Route::get("/hello").using(request_handler))
         .add(Route::from(Method::PATCH, "/world").using(request_handler))
-        .build();
+        .build()
+        .expect("Failed to build the router");
 
     Ok(RouterService::new(router))
 }
@@ -0,0 +1,148 @@
+extern crate futures;
+extern crate hyper;
+extern crate hyper_router;
+
+mod common;
+
+use common::{body_of, call, request};
+use futures::{Future, Stream};
+use hyper::{Body, Method, Request, Response, StatusCode};
+use hyper_router::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[test]
+fn test_async_handler() {
+    fn handle_echo(request: Request<Body>) -> ResponseFuture {
+        Box::new(
+            request
+                .into_body()
+                .concat2()
+                .map(|body| Response::new(Body::from(body))),
+        )
+    }
+
+    let router = RouterBuilder::new()
+        .add(Route::post("/echo").using(handle_echo))
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    let mut echo_request = request(Method::POST, "http://www.example.com/echo");
+    *echo_request.body_mut() = Body::from("ping");
+    assert_eq!(body_of(call(&mut service, echo_request)), "ping");
+}
+
+#[test]
+fn test_async_and_sync_handlers() {
+    fn handle_sync(_: Request<Body>) -> Response<Body> {
+        Response::new(Body::from("sync"))
+    }
+    fn handle_async(_: Request<Body>) -> ResponseFuture {
+        Box::new(futures::future::lazy(|| {
+            Ok(Response::builder()
+                .status(StatusCode::ACCEPTED)
+                .body(Body::from("async"))
+                .unwrap())
+        }))
+    }
+
+    let router = RouterBuilder::new()
+        .add(Route::get("/sync").using(handle_sync))
+        .add(Route::get("/async").using(handle_async))
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/sync"),
+    );
+    assert_eq!(body_of(response), "sync");
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/async"),
+    );
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    assert_eq!(body_of(response), "async");
+}
+
+#[test]
+fn test_closure_handler() {
+    let greeting = Arc::new("Hello".to_string());
+    let counter = Arc::new(AtomicUsize::new(0));
+
+    let handler_counter = counter.clone();
+    let router = RouterBuilder::new()
+        .add(
+            Route::get("/greet/:name").using(move |request: Request<Body>| {
+                handler_counter.fetch_add(1, Ordering::SeqCst);
+                let name = request.extensions().get::<Params>().unwrap().get("name");
+                Response::new(Body::from(format!("{} {}", greeting, name.unwrap())))
+            }),
+        )
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/greet/Bob"),
+    );
+    assert_eq!(body_of(response), "Hello Bob");
+    call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/greet/Alice"),
+    );
+    assert_eq!(counter.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_handler_trait_object() {
+    struct Greeter {
+        greeting: &'static str,
+    }
+
+    impl Handler for Greeter {
+        fn handle(&self, _: Request<Body>) -> ResponseFuture {
+            Box::new(futures::future::ok(Response::new(Body::from(
+                self.greeting,
+            ))))
+        }
+    }
+
+    let router = RouterBuilder::new()
+        .add(Route::get("/greet").using(Greeter { greeting: "Hi" }))
+        .build()
+        .unwrap();
+
+    let get_greet = request(Method::GET, "http://www.example.com/greet");
+    let handler = router.find_handler(&get_greet).unwrap();
+    assert_eq!(body_of(handler.handle(get_greet).wait().unwrap()), "Hi");
+}
+
+#[test]
+fn test_arc_handler() {
+    let shared = Arc::new(|_: Request<Body>| Response::new(Body::from("shared")));
+    let router = RouterBuilder::new()
+        .add(Route::get("/a").using_arc(shared.clone()))
+        .add(Route::get("/b").using_arc(shared))
+        .build()
+        .unwrap();
+
+    let found = router
+        .find_handler(&request(Method::GET, "http://www.example.com/a"))
+        .unwrap();
+    let router = RouterBuilder::new()
+        .add(Route::get("/c").using_arc(found))
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/c"),
+    );
+    assert_eq!(body_of(response), "shared");
+}
@@ -1,9 +1,18 @@
+extern crate futures;
 extern crate hyper;
 extern crate hyper_router;
 
+use futures::{Future, Stream};
 use hyper::{Body, Method, Request, Response, Uri};
 use hyper_router::*;
 use std::str::FromStr;
+use std::sync::Arc;
+
+fn assert_handler(handler: Arc<dyn Handler>, request: Request<Body>, expected: &str) {
+    let response = handler.handle(request).wait().unwrap();
+    let body = response.into_body().concat2().wait().unwrap();
+    assert_eq!(body.as_ref(), expected.as_bytes());
+}
 
 #[test]
 fn test_get_route() {
@@ -14,27 +23,28 @@ fn test_get_route() {
         .unwrap();
 
     fn handle_get_hello(_: Request<Body>) -> Response<Body> {
-        unimplemented!()
-    };
+        Response::new(Body::from("handle_get_hello"))
+    }
     fn handle_get_root(_: Request<Body>) -> Response<Body> {
         unimplemented!()
-    };
+    }
     fn handle_get_foo(_: Request<Body>) -> Response<Body> {
         unimplemented!()
-    };
+    }
     fn handle_post_hello(_: Request<Body>) -> Response<Body> {
-        unimplemented!()
-    };
+        Response::new(Body::from("handle_post_hello"))
+    }
 
     let router = RouterBuilder::new()
         .add(Route::get("/hello").using(handle_get_hello))
         .add(Route::get("/").using(handle_get_root))
         .add(Route::get("/foo").using(handle_get_foo))
         .add(Route::post("/hello").using(handle_post_hello))
-        .build();
+        .build()
+        .unwrap();
 
     let handler = router.find_handler(&request).unwrap();
-    assert!(handler as fn(_) -> _ == handle_get_hello as fn(_) -> _);
+    assert_handler(handler, request, "handle_get_hello");
 }
 
 #[test]
@@ -46,27 +56,28 @@ fn test_post_route() {
         .unwrap();
 
     fn handle_post_hello(_: Request<Body>) -> Response<Body> {
-        unimplemented!()
-    };
+        Response::new(Body::from("handle_post_hello"))
+    }
     fn handle_post_root(_: Request<Body>) -> Response<Body> {
         unimplemented!()
-    };
+    }
     fn handle_post_foo(_: Request<Body>) -> Response<Body> {
         unimplemented!()
-    };
+    }
     fn handle_get_hello(_: Request<Body>) -> Response<Body> {
-        unimplemented!()
-    };
+        Response::new(Body::from("handle_get_hello"))
+    }
 
     let router = RouterBuilder::new()
         .add(Route::post("/hello").using(handle_post_hello))
         .add(Route::get("/").using(handle_post_root))
         .add(Route::get("/foo").using(handle_post_foo))
         .add(Route::get("/hello").using(handle_get_hello))
-        .build();
+        .build()
+        .unwrap();
 
     let handler = router.find_handler(&request).unwrap();
-    assert!(handler as fn(_) -> _ == handle_post_hello as fn(_) -> _);
+    assert_handler(handler, request, "handle_post_hello");
 }
 
 #[test]
@@ -78,19 +89,20 @@ fn test_delete_route() {
         .unwrap();
 
     fn handle_delete_hello(_: Request<Body>) -> Response<Body> {
-        unimplemented!()
-    };
+        Response::new(Body::from("handle_delete_hello"))
+    }
     fn handle_post_hello(_: Request<Body>) -> Response<Body> {
-        unimplemented!()
-    };
+        Response::new(Body::from("handle_post_hello"))
+    }
 
     let router = RouterBuilder::new()
         .add(Route::delete("/hello").using(handle_delete_hello))
         .add(Route::post("/hello").using(handle_post_hello))
-        .build();
+        .build()
+        .unwrap();
 
     let handler = router.find_handler(&request).unwrap();
-    assert!(handler as fn(_) -> _ == handle_delete_hello as fn(_) -> _);
+    assert_handler(handler, request, "handle_delete_hello");
 }
 
 #[test]
@@ -102,19 +114,20 @@ fn test_options_route() {
         .unwrap();
 
     fn handle_options_hello(_: Request<Body>) -> Response<Body> {
-        unimplemented!()
-    };
+        Response::new(Body::from("handle_options_hello"))
+    }
     fn handle_post_hello(_: Request<Body>) -> Response<Body> {
-        unimplemented!()
-    };
+        Response::new(Body::from("handle_post_hello"))
+    }
 
     let router = RouterBuilder::new()
         .add(Route::options("/hello").using(handle_options_hello))
         .add(Route::post("/hello").using(handle_post_hello))
-        .build();
+        .build()
+        .unwrap();
 
     let handler = router.find_handler(&request).unwrap();
-    assert!(handler as fn(_) -> _ == handle_options_hello as fn(_) -> _);
+    assert_handler(handler, request, "handle_options_hello");
 }
 
 #[test]
@@ -126,19 +139,20 @@ fn test_put_route() {
         .unwrap();
 
     fn handle_put_hello(_: Request<Body>) -> Response<Body> {
-        unimplemented!()
-    };
+        Response::new(Body::from("handle_put_hello"))
+    }
     fn handle_post_hello(_: Request<Body>) -> Response<Body> {
-        unimplemented!()
-    };
+        Response::new(Body::from("handle_post_hello"))
+    }
 
     let router = RouterBuilder::new()
         .add(Route::put("/hello").using(handle_put_hello))
         .add(Route::post("/hello").using(handle_post_hello))
-        .build();
+        .build()
+        .unwrap();
 
     let handler = router.find_handler(&request).unwrap();
-    assert!(handler as fn(_) -> _ == handle_put_hello as fn(_) -> _);
+    assert_handler(handler, request, "handle_put_hello");
 }
 
 #[test]
@@ -150,19 +164,20 @@ fn test_head_route() {
         .unwrap();
 
     fn handle_head_hello(_: Request<Body>) -> Response<Body> {
-        unimplemented!()
-    };
+        Response::new(Body::from("handle_head_hello"))
+    }
     fn handle_post_hello(_: Request<Body>) -> Response<Body> {
-        unimplemented!()
-    };
+        Response::new(Body::from("handle_post_hello"))
+    }
 
     let router = RouterBuilder::new()
         .add(Route::head("/hello").using(handle_head_hello))
         .add(Route::post("/hello").using(handle_post_hello))
-        .build();
+        .build()
+        .unwrap();
 
     let handler = router.find_handler(&request).unwrap();
-    assert!(handler as fn(_) -> _ == handle_head_hello as fn(_) -> _);
+    assert_handler(handler, request, "handle_head_hello");
 }
 
 #[test]
@@ -174,19 +189,20 @@ fn test_trace_route() {
         .unwrap();
 
     fn handle_trace_hello(_: Request<Body>) -> Response<Body> {
-        unimplemented!()
-    };
+        Response::new(Body::from("handle_trace_hello"))
+    }
     fn handle_post_hello(_: Request<Body>) -> Response<Body> {
-        unimplemented!()
-    };
+        Response::new(Body::from("handle_post_hello"))
+    }
 
     let router = RouterBuilder::new()
         .add(Route::trace("/hello").using(handle_trace_hello))
         .add(Route::post("/hello").using(handle_post_hello))
-        .build();
+        .build()
+        .unwrap();
 
     let handler = router.find_handler(&request).unwrap();
-    assert!(handler as fn(_) -> _ == handle_trace_hello as fn(_) -> _);
+    assert_handler(handler, request, "handle_trace_hello");
 }
 
 #[test]
@@ -198,19 +214,20 @@ fn test_patch_route() {
         .unwrap();
 
     fn handle_patch_hello(_: Request<Body>) -> Response<Body> {
-        unimplemented!()
-    };
+        Response::new(Body::from("handle_patch_hello"))
+    }
     fn handle_post_hello(_: Request<Body>) -> Response<Body> {
-        unimplemented!()
-    };
+        Response::new(Body::from("handle_post_hello"))
+    }
 
     let router = RouterBuilder::new()
         .add(Route::patch("/hello").using(handle_patch_hello))
         .add(Route::post("/hello").using(handle_post_hello))
-        .build();
+        .build()
+        .unwrap();
 
     let handler = router.find_handler(&request).unwrap();
-    assert!(handler as fn(_) -> _ == handle_patch_hello as fn(_) -> _);
+    assert_handler(handler, request, "handle_patch_hello");
 }
 
 #[test]
@@ -223,15 +240,16 @@ fn test_no_route() {
 
     fn handle_get_foo(_: Request<Body>) -> Response<Body> {
         unimplemented!()
-    };
+    }
     fn handle_get_bar(_: Request<Body>) -> Response<Body> {
         unimplemented!()
-    };
+    }
 
     let router = RouterBuilder::new()
         .add(Route::patch("/foo").using(handle_get_foo))
         .add(Route::patch("/bar").using(handle_get_bar))
-        .build();
+        .build()
+        .unwrap();
 
     let handler = router.find_handler(&request);
 
@@ -250,17 +268,18 @@ fn test_regex_path() {
         .unwrap();
 
     fn handle_regex_foo(_: Request<Body>) -> Response<Body> {
-        unimplemented!()
-    };
+        Response::new(Body::from("handle_regex_foo"))
+    }
     fn handle_regex_bar(_: Request<Body>) -> Response<Body> {
         unimplemented!()
-    };
+    }
 
     let router = RouterBuilder::new()
         .add(Route::get(r"/foo/.*?").using(handle_regex_foo))
         .add(Route::get(r"/bar/.*?").using(handle_regex_bar))
-        .build();
+        .build()
+        .unwrap();
 
     let handler = router.find_handler(&request).unwrap();
-    assert!(handler as fn(_) -> _ == handle_regex_foo as fn(_) -> _);
+    assert_handler(handler, request, "handle_regex_foo");
 }
@@ -0,0 +1,148 @@
+extern crate futures;
+extern crate hyper;
+extern crate hyper_router;
+
+mod common;
+
+use common::{body_of, echo_params, send};
+use hyper::{Body, Method, Request, Response, StatusCode};
+use hyper_router::*;
+
+#[test]
+fn test_case_insensitive_route() {
+    let router = RouterBuilder::new()
+        .add(
+            Route::get("/Users/:name")
+                .case_insensitive()
+                .using(echo_params),
+        )
+        .add(Route::get("/posts/:id").using(echo_params))
+        .add(
+            Route::get(r"/files/(?P<file>\w+)\.txt")
+                .case_insensitive()
+                .using(echo_params),
+        )
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    assert_eq!(
+        body_of(send(&mut service, Method::GET, "/users/Ann")),
+        "name=Ann"
+    );
+    assert_eq!(
+        body_of(send(&mut service, Method::GET, "/USERS/ann")),
+        "name=ann"
+    );
+    assert_eq!(
+        body_of(send(&mut service, Method::GET, "/Files/Notes.TXT")),
+        "file=Notes"
+    );
+    assert_eq!(
+        body_of(send(&mut service, Method::GET, "/posts/A1")),
+        "id=A1"
+    );
+    assert_eq!(
+        send(&mut service, Method::GET, "/Posts/A1").status(),
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[test]
+fn test_case_insensitive_router() {
+    let admin = RouterBuilder::new()
+        .add(Route::get("/settings").using(echo_params))
+        .build()
+        .unwrap();
+    let router = RouterBuilder::new()
+        .case_insensitive(true)
+        .scope("/api", |api| {
+            api.add(Route::get("/items/{id:u64}").using(echo_params))
+        })
+        .mount("/admin", admin)
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    assert_eq!(
+        body_of(send(&mut service, Method::GET, "/API/Items/7")),
+        "id=7"
+    );
+    assert_eq!(
+        send(&mut service, Method::GET, "/Admin/settings").status(),
+        StatusCode::OK
+    );
+    assert_eq!(
+        send(&mut service, Method::GET, "/admin/SETTINGS").status(),
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[test]
+fn test_case_insensitive_conflicts() {
+    let router = RouterBuilder::new()
+        .case_insensitive(true)
+        .add(Route::get("/users").using(echo_params))
+        .add(Route::get("/USERS").using(echo_params))
+        .build()
+        .unwrap();
+    assert_eq!(router.conflicts().len(), 1);
+
+    let router = RouterBuilder::new()
+        .add(Route::get("/users").using(echo_params))
+        .add(Route::get("/users").case_insensitive().using(echo_params))
+        .add(Route::get("/:name").using(echo_params))
+        .add(
+            Route::get("/{slug:slug}")
+                .case_insensitive()
+                .using(echo_params),
+        )
+        .build()
+        .unwrap();
+    assert!(router.conflicts().is_empty());
+}
+
+#[test]
+fn test_prefix_middleware_applies_to_case_insensitive_routes() {
+    fn deny(_: Request<Body>, _: Next) -> Response<Body> {
+        Response::builder()
+            .status(StatusCode::FORBIDDEN)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    let router = RouterBuilder::new()
+        .middleware_at("/admin", deny)
+        .add(
+            Route::get("/admin/users")
+                .case_insensitive()
+                .using(echo_params),
+        )
+        .scope("/staff", |staff| {
+            staff
+                .middleware(deny)
+                .add(Route::get("/users").case_insensitive().using(echo_params))
+        })
+        .add(Route::get("/Admin/about").using(echo_params))
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    for path in &[
+        "/admin/users",
+        "/ADMIN/users",
+        "/staff/users",
+        "/Staff/USERS",
+    ] {
+        assert_eq!(
+            send(&mut service, Method::GET, path).status(),
+            StatusCode::FORBIDDEN,
+            "{}",
+            path
+        );
+    }
+    assert_eq!(
+        send(&mut service, Method::GET, "/Admin/about").status(),
+        StatusCode::OK
+    );
+}
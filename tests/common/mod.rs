@@ -0,0 +1,51 @@
+#![allow(dead_code)]
+
+use futures::{Future, Stream};
+use hyper::service::Service;
+use hyper::{Body, Method, Request, Response, Uri};
+use hyper_router::{Params, RouterService};
+use std::str::FromStr;
+
+pub fn request(method: Method, uri: &str) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(Uri::from_str(uri).unwrap())
+        .body(Body::empty())
+        .unwrap()
+}
+
+pub fn call(service: &mut RouterService, request: Request<Body>) -> Response<Body> {
+    service.call(request).wait().unwrap()
+}
+
+/// Calls the service with a request for given path of `www.example.com`.
+pub fn send(service: &mut RouterService, method: Method, path: &str) -> Response<Body> {
+    call(
+        service,
+        request(method, &format!("http://www.example.com{}", path)),
+    )
+}
+
+pub fn body_of(response: Response<Body>) -> String {
+    let body = response.into_body().concat2().wait().unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+/// Handler responding with the parameters of the request, as `name=value` pairs joined by `&`.
+pub fn echo_params(request: Request<Body>) -> Response<Body> {
+    let params = request.extensions().get::<Params>().unwrap();
+    let values: Vec<String> = params
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect();
+    Response::new(Body::from(values.join("&")))
+}
+
+pub fn handle_any(_: Request<Body>) -> Response<Body> {
+    Response::new(Body::from("handled"))
+}
+
+/// Returns a handler responding with given body.
+pub fn handle_with(body: &'static str) -> impl Fn(Request<Body>) -> Response<Body> {
+    move |_: Request<Body>| Response::new(Body::from(body))
+}
@@ -0,0 +1,120 @@
+extern crate futures;
+extern crate hyper;
+extern crate hyper_router;
+
+mod common;
+
+use common::handle_any;
+use hyper::Method;
+use hyper_router::*;
+
+fn shadowed_by(router: &Router, pattern: &str) -> Option<String> {
+    router
+        .conflicts()
+        .iter()
+        .find_map(|conflict| match conflict {
+            RouterError::ShadowedRoute {
+                pattern: shadowed,
+                shadowed_by,
+                ..
+            } if shadowed == pattern => Some(shadowed_by.clone()),
+            _ => None,
+        })
+}
+
+#[test]
+fn test_duplicate_routes() {
+    let router = RouterBuilder::new()
+        .add(Route::get("/users").using(handle_any))
+        .add(Route::post("/users").using(handle_any))
+        .add(Route::get("/users").name("users").using(handle_any))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        router.conflicts(),
+        &[RouterError::DuplicateRoute {
+            route: Some("users".to_string()),
+            method: Method::GET,
+            pattern: "/users".to_string(),
+        }]
+    );
+    assert_eq!(
+        router.conflicts()[0].to_string(),
+        "duplicate route GET `/users` named `users`"
+    );
+}
+
+#[test]
+fn test_shadowed_routes() {
+    let router = RouterBuilder::new()
+        .add(Route::get("/user/.*").using(handle_any))
+        .add(Route::get("/user/me").using(handle_any))
+        .add(Route::get("/post/:id").using(handle_any))
+        .add(Route::get("/post/{id:u64}").using(handle_any))
+        .add(Route::get("/post/{slug:slug}/comments").using(handle_any))
+        .add(Route::get("/post/latest").using(handle_any))
+        .add(Route::get("/item/{id:u8}").using(handle_any))
+        .add(Route::get("/item/300").using(handle_any))
+        .add(Route::get("/item/12").using(handle_any))
+        .add(Route::post("/user/you").using(handle_any))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        shadowed_by(&router, "/user/me"),
+        Some("/user/.*".to_string())
+    );
+    assert_eq!(
+        shadowed_by(&router, "/post/{id:u64}"),
+        Some("/post/:id".to_string())
+    );
+    assert_eq!(
+        shadowed_by(&router, "/post/latest"),
+        Some("/post/:id".to_string())
+    );
+    assert_eq!(
+        shadowed_by(&router, "/item/12"),
+        Some("/item/{id:u8}".to_string())
+    );
+    assert_eq!(shadowed_by(&router, "/post/{slug:slug}/comments"), None);
+    assert_eq!(shadowed_by(&router, "/item/300"), None);
+    assert_eq!(shadowed_by(&router, "/user/you"), None);
+    assert_eq!(router.conflicts().len(), 4);
+}
+
+#[test]
+fn test_undecidable_routes_are_not_reported() {
+    let router = RouterBuilder::new()
+        .add(Route::get(r"/user/\d+").using(handle_any))
+        .add(Route::get(r"/user/[0-9]+").using(handle_any))
+        .add(Route::get("/robots.txt").using(handle_any))
+        .add(Route::get("/robots.txt/.*").using(handle_any))
+        .build()
+        .unwrap();
+
+    assert!(router.conflicts().is_empty());
+}
+
+#[test]
+fn test_strict_mode_fails_build() {
+    let error = RouterBuilder::new()
+        .strict(true)
+        .add(Route::get("/user/:id").using(handle_any))
+        .add(Route::get("/user/me").name("me").using(handle_any))
+        .build()
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "route GET `/user/me` named `me` is shadowed by earlier route `/user/:id`"
+    );
+
+    let router = RouterBuilder::new()
+        .strict(true)
+        .add(Route::get("/user/me").using(handle_any))
+        .add(Route::get("/user/:id").using(handle_any))
+        .build()
+        .unwrap();
+    assert!(router.conflicts().is_empty());
+}
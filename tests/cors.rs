@@ -0,0 +1,277 @@
+extern crate futures;
+extern crate hyper;
+extern crate hyper_router;
+
+mod common;
+
+use common::{body_of, call, handle_any, request};
+use hyper::header::{
+    ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS,
+    ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS, ACCESS_CONTROL_MAX_AGE,
+    ACCESS_CONTROL_REQUEST_HEADERS, ACCESS_CONTROL_REQUEST_METHOD, ALLOW, ORIGIN, VARY,
+};
+use hyper::{Body, Method, Request, Response, StatusCode};
+use hyper_router::*;
+use std::time::Duration;
+
+fn reject_all(_: Request<Body>, _: Next) -> ResponseFuture {
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = StatusCode::UNAUTHORIZED;
+    Box::new(futures::future::ok(response))
+}
+
+fn router(cors: Cors) -> Router {
+    RouterBuilder::new()
+        .cors(cors)
+        .add(Route::get("/users").using(handle_any))
+        .add(Route::post("/users").using(handle_any))
+        .build()
+        .unwrap()
+}
+
+fn from_origin(method: Method, uri: &str, origin: &str) -> Request<Body> {
+    let mut request = request(method, uri);
+    request
+        .headers_mut()
+        .insert(ORIGIN, origin.parse().unwrap());
+    request
+}
+
+fn preflight(uri: &str, origin: &str, method: &str) -> Request<Body> {
+    let mut request = from_origin(Method::OPTIONS, uri, origin);
+    request
+        .headers_mut()
+        .insert(ACCESS_CONTROL_REQUEST_METHOD, method.parse().unwrap());
+    request
+}
+
+#[test]
+fn test_preflight_uses_route_methods() {
+    let mut service = RouterService::new(router(Cors::new().max_age(Duration::from_secs(600))));
+
+    let mut options = preflight(
+        "http://api.example.com/users",
+        "https://example.com",
+        "POST",
+    );
+    options.headers_mut().insert(
+        ACCESS_CONTROL_REQUEST_HEADERS,
+        "content-type".parse().unwrap(),
+    );
+    let response = call(&mut service, options);
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let headers = response.headers();
+    assert_eq!(headers[ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+    assert_eq!(
+        headers[ACCESS_CONTROL_ALLOW_METHODS],
+        "GET, HEAD, POST, OPTIONS"
+    );
+    assert_eq!(headers[ACCESS_CONTROL_ALLOW_HEADERS], "content-type");
+    assert_eq!(headers[ACCESS_CONTROL_MAX_AGE], "600");
+
+    let response = call(
+        &mut service,
+        preflight(
+            "http://api.example.com/users",
+            "https://example.com",
+            "DELETE",
+        ),
+    );
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert!(!response.headers().contains_key(ACCESS_CONTROL_ALLOW_ORIGIN));
+    assert_eq!(response.headers()[ALLOW], "GET, HEAD, POST, OPTIONS");
+
+    let response = call(
+        &mut service,
+        preflight(
+            "http://api.example.com/missing",
+            "https://example.com",
+            "GET",
+        ),
+    );
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn test_allowed_origins() {
+    let cors = Cors::new()
+        .allow_origins(&["https://example.com", "https://example.org"])
+        .allow_origin_regex(r"https://[a-z]+\.example\.com")
+        .allow_credentials(true)
+        .expose_headers(&["x-request-id"]);
+    let mut service = RouterService::new(router(cors));
+
+    for origin in &[
+        "https://example.com",
+        "https://example.org",
+        "https://admin.example.com",
+    ] {
+        let response = call(
+            &mut service,
+            from_origin(Method::GET, "http://api.example.com/users", origin),
+        );
+        let headers = response.headers();
+        assert_eq!(headers[ACCESS_CONTROL_ALLOW_ORIGIN], *origin);
+        assert_eq!(headers[ACCESS_CONTROL_ALLOW_CREDENTIALS], "true");
+        assert_eq!(headers[ACCESS_CONTROL_EXPOSE_HEADERS], "x-request-id");
+        assert_eq!(headers[VARY], "Origin");
+        assert_eq!(body_of(response), "handled");
+    }
+
+    let response = call(
+        &mut service,
+        from_origin(
+            Method::GET,
+            "http://api.example.com/users",
+            "https://evil.com",
+        ),
+    );
+    assert!(!response.headers().contains_key(ACCESS_CONTROL_ALLOW_ORIGIN));
+    assert_eq!(response.headers()[VARY], "Origin");
+    assert_eq!(body_of(response), "handled");
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://api.example.com/users"),
+    );
+    assert!(!response.headers().contains_key(ACCESS_CONTROL_ALLOW_ORIGIN));
+    assert_eq!(response.headers()[VARY], "Origin");
+
+    let response = call(
+        &mut service,
+        preflight(
+            "http://api.example.com/users",
+            "https://evil.example.com.net",
+            "GET",
+        ),
+    );
+    assert!(!response.headers().contains_key(ACCESS_CONTROL_ALLOW_ORIGIN));
+    assert_eq!(response.headers()[VARY], "Origin");
+}
+
+#[test]
+fn test_origin_regex_alternation_is_anchored() {
+    let cors = Cors::new()
+        .allow_origin_regex(r"https://a\.com|https://b\.com")
+        .allow_credentials(true);
+    let mut service = RouterService::new(router(cors));
+
+    for origin in &["https://a.com", "https://b.com"] {
+        let response = call(
+            &mut service,
+            from_origin(Method::GET, "http://api.example.com/users", origin),
+        );
+        assert_eq!(response.headers()[ACCESS_CONTROL_ALLOW_ORIGIN], *origin);
+    }
+    for origin in &["https://a.com.evil.net", "https://evil.net/https://b.com"] {
+        let response = call(
+            &mut service,
+            from_origin(Method::GET, "http://api.example.com/users", origin),
+        );
+        assert!(!response.headers().contains_key(ACCESS_CONTROL_ALLOW_ORIGIN));
+        assert!(!response
+            .headers()
+            .contains_key(ACCESS_CONTROL_ALLOW_CREDENTIALS));
+    }
+}
+
+#[test]
+fn test_credentials_require_explicit_origins() {
+    let error = RouterBuilder::new()
+        .cors(Cors::new().allow_credentials(true))
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        error,
+        RouterError::InvalidCors(CorsError::CredentialsWithAnyOrigin)
+    );
+    assert_eq!(
+        error.to_string(),
+        "invalid CORS configuration: credentials cannot be allowed for any origin"
+    );
+}
+
+#[test]
+fn test_invalid_settings_are_reported_by_build() {
+    let build = |cors: Cors| RouterBuilder::new().cors(cors).build().unwrap_err();
+
+    match build(Cors::new().allow_origin_regex("(unclosed")) {
+        RouterError::InvalidCors(CorsError::InvalidOriginRegex { pattern, .. }) => {
+            assert_eq!(pattern, "(unclosed")
+        }
+        other => panic!("Expected an invalid origin pattern error, got {:?}", other),
+    }
+    assert_eq!(
+        build(Cors::new().allow_headers(&["content-type", "bad header"])),
+        RouterError::InvalidCors(CorsError::InvalidHeader("bad header".to_string()))
+    );
+    assert_eq!(
+        build(Cors::new().expose_headers(&["x-id\n"])).to_string(),
+        "invalid CORS configuration: invalid header name `x-id\n`"
+    );
+}
+
+#[test]
+fn test_configured_methods_and_headers() {
+    let cors = Cors::new()
+        .allow_methods(&[Method::GET])
+        .allow_headers(&["content-type", "authorization"]);
+    let mut service = RouterService::new(router(cors));
+
+    let response = call(
+        &mut service,
+        preflight("http://api.example.com/users", "https://example.com", "GET"),
+    );
+    let headers = response.headers();
+    assert_eq!(headers[ACCESS_CONTROL_ALLOW_METHODS], "GET");
+    assert_eq!(
+        headers[ACCESS_CONTROL_ALLOW_HEADERS],
+        "content-type, authorization"
+    );
+
+    let response = call(
+        &mut service,
+        preflight(
+            "http://api.example.com/users",
+            "https://example.com",
+            "POST",
+        ),
+    );
+    assert!(!response
+        .headers()
+        .contains_key(ACCESS_CONTROL_ALLOW_METHODS));
+}
+
+#[test]
+fn test_preflight_skips_middleware() {
+    let router = RouterBuilder::new()
+        .cors(Cors::new())
+        .middleware(reject_all)
+        .add(Route::get("/users").using(handle_any))
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    let response = call(
+        &mut service,
+        preflight("http://api.example.com/users", "https://example.com", "GET"),
+    );
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let response = call(
+        &mut service,
+        from_origin(
+            Method::GET,
+            "http://api.example.com/users",
+            "https://example.com",
+        ),
+    );
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(response.headers()[ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://api.example.com/users"),
+    );
+    assert!(!response.headers().contains_key(VARY));
+}
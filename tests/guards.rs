@@ -0,0 +1,140 @@
+extern crate futures;
+extern crate hyper;
+extern crate hyper_router;
+
+mod common;
+
+use common::{body_of, call, handle_with, request};
+use hyper::header::ALLOW;
+use hyper::{Body, Method, Request, StatusCode};
+use hyper_router::*;
+
+fn request_with(method: Method, uri: &str, headers: &[(&str, &str)]) -> Request<Body> {
+    let mut builder = Request::builder();
+    builder.method(method).uri(uri);
+    for (name, value) in headers {
+        builder.header(*name, *value);
+    }
+    builder.body(Body::empty()).unwrap()
+}
+
+fn router() -> Router {
+    RouterBuilder::new()
+        .add(
+            Route::get("/items")
+                .guard(Guard::header_eq("accept-version", "2"))
+                .using(handle_with("v2")),
+        )
+        .add(
+            Route::get("/items")
+                .guard(Guard::header_matches("accept-version", r"1(\.\d+)?"))
+                .using(handle_with("v1")),
+        )
+        .add(Route::get("/items").using(handle_with("latest")))
+        .add(
+            Route::post("/hooks")
+                .guard(Guard::header("x-hub-signature"))
+                .using(handle_with("hook")),
+        )
+        .add(
+            Route::get("/search")
+                .guard(Guard::query("q"))
+                .guard(Guard::custom(|request| {
+                    request.headers().contains_key("x-beta")
+                }))
+                .using(handle_with("search")),
+        )
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_header_guards() {
+    let mut service = RouterService::new(router());
+
+    let versions = [("2", "v2"), ("1", "v1"), ("1.3", "v1"), ("3", "latest")];
+    for (version, body) in &versions {
+        let response = call(
+            &mut service,
+            request_with(Method::GET, "/items", &[("accept-version", version)]),
+        );
+        assert_eq!(body_of(response), *body, "version {}", version);
+    }
+    let response = call(&mut service, request(Method::GET, "/items"));
+    assert_eq!(body_of(response), "latest");
+
+    let hook = request_with(Method::POST, "/hooks", &[("x-hub-signature", "sha1=abc")]);
+    assert_eq!(body_of(call(&mut service, hook)), "hook");
+    let response = call(&mut service, request(Method::POST, "/hooks"));
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn test_query_and_custom_guards() {
+    let router = router();
+    let beta = [("x-beta", "1")];
+
+    let search = request_with(Method::GET, "/search?page=2&q=rust", &beta);
+    assert!(router.find_handler(&search).is_ok());
+    let search = request_with(Method::GET, "/search?q", &beta);
+    assert!(router.find_handler(&search).is_ok());
+    let search = request_with(Method::GET, "/search?query=rust", &beta);
+    assert_eq!(
+        router.find_handler(&search).err(),
+        Some(StatusCode::NOT_FOUND)
+    );
+    let search = request(Method::GET, "/search?q=rust");
+    assert_eq!(
+        router.find_handler(&search).err(),
+        Some(StatusCode::NOT_FOUND)
+    );
+}
+
+#[test]
+fn test_allowed_methods_respect_guards() {
+    let mut service = RouterService::new(router());
+
+    let response = call(
+        &mut service,
+        request_with(Method::DELETE, "/hooks", &[("x-hub-signature", "sha1=abc")]),
+    );
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(response.headers()[ALLOW], "POST, OPTIONS");
+
+    let response = call(&mut service, request(Method::DELETE, "/hooks"));
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn test_invalid_header_pattern() {
+    let error = RouterBuilder::new()
+        .add(
+            Route::get("/items")
+                .name("items")
+                .guard(Guard::header_matches("accept-version", "(1"))
+                .using(handle_with("v1")),
+        )
+        .build()
+        .unwrap_err();
+    match &error {
+        RouterError::InvalidGuard {
+            route,
+            method,
+            pattern,
+            ..
+        } => {
+            assert_eq!(route.as_ref().map(String::as_str), Some("items"));
+            assert_eq!(method, Method::GET);
+            assert_eq!(pattern, "(1");
+        }
+        _ => panic!("Expected an invalid guard error"),
+    }
+    assert!(error
+        .to_string()
+        .starts_with("invalid header pattern `(1` of route GET named `items`: "));
+}
+
+#[test]
+fn test_guarded_routes_do_not_shadow() {
+    assert!(router().conflicts().is_empty());
+}
@@ -0,0 +1,84 @@
+extern crate futures;
+extern crate hyper;
+extern crate hyper_router;
+
+mod common;
+
+use common::{body_of, call, request};
+use hyper::header::{CONTENT_LENGTH, CONTENT_TYPE};
+use hyper::{Body, Method, Request, Response, StatusCode};
+use hyper_router::*;
+
+fn handle_get(_: Request<Body>) -> Response<Body> {
+    Response::builder()
+        .header(CONTENT_TYPE, "text/plain")
+        .body(Body::from("Hello World"))
+        .unwrap()
+}
+
+fn handle_head(_: Request<Body>) -> Response<Body> {
+    Response::builder()
+        .header("x-explicit", "yes")
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[test]
+fn test_head_served_by_get_route() {
+    let router = RouterBuilder::new()
+        .add(Route::get("/hello").using(handle_get))
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    let response = call(
+        &mut service,
+        request(Method::HEAD, "http://www.example.com/hello"),
+    );
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[CONTENT_TYPE], "text/plain");
+    assert_eq!(response.headers()[CONTENT_LENGTH], "11");
+    assert_eq!(body_of(response), "");
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/hello"),
+    );
+    assert_eq!(body_of(response), "Hello World");
+}
+
+#[test]
+fn test_explicit_head_route_takes_priority() {
+    let router = RouterBuilder::new()
+        .add(Route::get("/hello").using(handle_get))
+        .mount(
+            "/",
+            RouterBuilder::new()
+                .add(Route::head("/hello").using(handle_head))
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    let response = call(
+        &mut service,
+        request(Method::HEAD, "http://www.example.com/hello"),
+    );
+    assert_eq!(response.headers()["x-explicit"], "yes");
+}
+
+#[test]
+fn test_head_without_get_route() {
+    let router = RouterBuilder::new()
+        .add(Route::post("/hello").using(handle_get))
+        .build()
+        .unwrap();
+
+    let result = router.find_route(&request(Method::HEAD, "http://www.example.com/hello"));
+    assert_eq!(result.unwrap_err(), StatusCode::METHOD_NOT_ALLOWED);
+
+    let result = router.find_route(&request(Method::HEAD, "http://www.example.com/missing"));
+    assert_eq!(result.unwrap_err(), StatusCode::NOT_FOUND);
+}
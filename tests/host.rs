@@ -0,0 +1,160 @@
+extern crate futures;
+extern crate hyper;
+extern crate hyper_router;
+
+mod common;
+
+use common::{body_of, call, echo_params, handle_with, request};
+use hyper::header::{ALLOW, HOST};
+use hyper::{Body, Method, Request, StatusCode};
+use hyper_router::*;
+
+fn router(host_fallback: HostFallback) -> Router {
+    let tenants = RouterBuilder::new()
+        .add(Route::get("/users/:id").using(echo_params))
+        .build()
+        .unwrap();
+    RouterBuilder::new()
+        .host_fallback(host_fallback)
+        .add(
+            Route::get("/status")
+                .host("api.example.com")
+                .using(handle_with("api")),
+        )
+        .add(
+            Route::post("/status")
+                .host("api.example.com")
+                .using(handle_with("api")),
+        )
+        .add(Route::get("/status").using(handle_with("any")))
+        .host("{tenant}.example.com", tenants)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_host_routes() {
+    let mut service = RouterService::new(router(HostFallback::default()));
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://API.example.com:8080/status"),
+    );
+    assert_eq!(body_of(response), "api");
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.org/status"),
+    );
+    assert_eq!(body_of(response), "any");
+
+    let response = call(
+        &mut service,
+        request(Method::DELETE, "http://api.example.com/status"),
+    );
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(response.headers()[ALLOW], "GET, HEAD, POST, OPTIONS");
+    let response = call(
+        &mut service,
+        request(Method::DELETE, "http://www.example.org/status"),
+    );
+    assert_eq!(response.headers()[ALLOW], "GET, HEAD, OPTIONS");
+}
+
+#[test]
+fn test_host_captures() {
+    let mut service = RouterService::new(router(HostFallback::default()));
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://acme.example.com/users/7"),
+    );
+    assert_eq!(body_of(response), "tenant=acme&id=7");
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://a.b.example.com/users/7"),
+    );
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let host = Host::new("{region}.{tenant}.example.com");
+    assert_eq!(
+        host.params("EU.Acme.example.com.").unwrap().get("tenant"),
+        Some("acme")
+    );
+    assert!(Host::new("*.example.com").is_match("cdn.example.com"));
+    assert!(!Host::new("*.example.com").is_match("example.com"));
+}
+
+#[test]
+fn test_host_header() {
+    let router = router(HostFallback::default());
+    let request = Request::builder()
+        .uri("/users/3")
+        .header(HOST, "shop.example.com:443")
+        .body(Body::empty())
+        .unwrap();
+    let route_match = router.find_route(&request).unwrap();
+    assert_eq!(route_match.params.get("tenant"), Some("shop"));
+
+    let request = Request::builder()
+        .uri("/users/3")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(
+        router.find_route(&request).err(),
+        Some(StatusCode::NOT_FOUND)
+    );
+}
+
+#[test]
+fn test_misdirected_request_fallback() {
+    let mut service = RouterService::new(router(HostFallback::MisdirectedRequest));
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.org/status"),
+    );
+    assert_eq!(response.status(), StatusCode::MISDIRECTED_REQUEST);
+    assert_eq!(body_of(response), "Routing error: misdirected request");
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://api.example.com/status"),
+    );
+    assert_eq!(body_of(response), "api");
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://acme.example.com/status"),
+    );
+    assert_eq!(body_of(response), "any");
+}
+
+#[test]
+fn test_same_path_on_different_hosts_is_no_conflict() {
+    let router = RouterBuilder::new()
+        .add(
+            Route::get("/")
+                .host("a.example.com")
+                .using(handle_with("a")),
+        )
+        .add(
+            Route::get("/")
+                .host("b.example.com")
+                .using(handle_with("b")),
+        )
+        .add(Route::get("/").using(handle_with("any")))
+        .add(
+            Route::get("/")
+                .host("c.example.com")
+                .using(handle_with("c")),
+        )
+        .build()
+        .unwrap();
+    let shadowed: Vec<Option<&str>> = router
+        .conflicts()
+        .iter()
+        .map(|conflict| match conflict {
+            RouterError::DuplicateRoute { .. } => Some("duplicate"),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(shadowed, vec![Some("duplicate")]);
+}
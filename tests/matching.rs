@@ -0,0 +1,127 @@
+extern crate futures;
+extern crate hyper;
+extern crate hyper_router;
+
+mod common;
+
+use common::handle_any;
+use hyper_router::*;
+
+const PATTERNS: &[&str] = &[
+    "/",
+    "/users",
+    "/users/",
+    "/users/:id",
+    "/users/{id:u8}",
+    "/users/{id:i64}/posts/{slug:slug}",
+    "/users/me",
+    "/users/:id/posts/:post",
+    r"/users/\d+",
+    "/users/.*",
+    "/files/{name}",
+    "/files/{flag:bool}",
+    "/files/{id:uuid}",
+    r"/files/(?P<name>\w+)\.txt",
+    "/files/readme.txt",
+    r"/static\.files/:name",
+    "/files/{id:u8}/(?P<rest>.*)",
+    r"/names/\w+",
+    "/assets/*file",
+    "/assets/css/**",
+    "/",
+];
+
+const PATHS: &[&str] = &[
+    "/",
+    "",
+    "/users",
+    "/users/",
+    "/users/12",
+    "/users/300",
+    "/users/-5/posts/hello-world",
+    "/users/-5/posts/Hello",
+    "/users/me",
+    "/users/me/posts/1",
+    "/users//posts/1",
+    "/users/12/extra",
+    "/files/true",
+    "/files/readme.txt",
+    "/files/readmeXtxt",
+    "/files/123e4567-e89b-12d3-a456-426614174000",
+    "/static.files/a",
+    "/files/12/a/b",
+    "/files/300/a/b",
+    "/names/zoe",
+    "/names/zoé",
+    "/assets",
+    "/assets/",
+    "/assets/css",
+    "/assets/css/",
+    "/assets/css/site/main.css",
+    "/staticXfiles/a",
+    "/missing",
+];
+
+#[test]
+fn test_matching_routes_agree_with_path_regexes() {
+    let router = PATTERNS
+        .iter()
+        .fold(RouterBuilder::new(), |builder, pattern| {
+            builder.add(Route::get(pattern).using(handle_any))
+        })
+        .build()
+        .unwrap();
+
+    for path in PATHS {
+        let expected: Vec<&str> = PATTERNS
+            .iter()
+            .filter(|pattern| Path::new(pattern).is_match(path))
+            .cloned()
+            .collect();
+        let found: Vec<&str> = router
+            .find_matching_routes(path)
+            .into_iter()
+            .map(|route| route.path.pattern())
+            .collect();
+        assert_eq!(found, expected, "routes matching `{}`", path);
+    }
+}
+
+#[test]
+fn test_case_insensitive_matching_agrees_with_path_regexes() {
+    let router = PATTERNS
+        .iter()
+        .fold(RouterBuilder::new(), |builder, pattern| {
+            builder.add(Route::get(pattern).using(handle_any))
+        })
+        .case_insensitive(true)
+        .build()
+        .unwrap();
+
+    let mixed_case = [
+        "/USERS",
+        "/Users/Me",
+        "/users/ME/Posts/1",
+        "/Users/-5/POSTS/hello-world",
+        "/FILES/README.TXT",
+        "/Files/True",
+        "/STATIC.FILES/A",
+        "/Assets/CSS/site.css",
+        "/names/ZOÉ",
+        "/users/\u{212A}",
+        "/u\u{17F}ers/me",
+    ];
+    for path in PATHS.iter().chain(&mixed_case) {
+        let expected: Vec<&str> = PATTERNS
+            .iter()
+            .filter(|pattern| Path::new(pattern).case_insensitive().is_match(path))
+            .cloned()
+            .collect();
+        let found: Vec<&str> = router
+            .find_matching_routes(path)
+            .into_iter()
+            .map(|route| route.path.pattern())
+            .collect();
+        assert_eq!(found, expected, "routes matching `{}`", path);
+    }
+}
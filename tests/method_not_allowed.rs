@@ -0,0 +1,103 @@
+extern crate futures;
+extern crate hyper;
+extern crate hyper_router;
+
+mod common;
+
+use common::{body_of, call, handle_any, request};
+use futures::Future;
+use hyper::header::ALLOW;
+use hyper::{Body, Method, Response, StatusCode};
+use hyper_router::*;
+
+fn router() -> Router {
+    RouterBuilder::new()
+        .add(Route::get("/hello").using(handle_any))
+        .add(Route::post("/hello").using(handle_any))
+        .add(Route::get("/h.*").using(handle_any))
+        .add(Route::delete("/other").using(handle_any))
+        .mount(
+            "/",
+            RouterBuilder::new()
+                .add(Route::put("/hello").using(handle_any))
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_method_not_allowed_response() {
+    let mut service = RouterService::new(router());
+
+    let response = call(
+        &mut service,
+        request(Method::PATCH, "http://www.example.com/hello"),
+    );
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(response.headers()[ALLOW], "GET, HEAD, POST, PUT, OPTIONS");
+    assert_eq!(body_of(response), "Routing error: method not allowed");
+
+    let response = call(
+        &mut service,
+        request(Method::PATCH, "http://www.example.com/missing"),
+    );
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert!(!response.headers().contains_key(ALLOW));
+}
+
+#[test]
+fn test_allow_header_with_custom_error_handler() {
+    fn error_handler(status_code: StatusCode) -> Response<Body> {
+        Response::builder()
+            .status(status_code)
+            .body(Body::from("custom"))
+            .unwrap()
+    }
+
+    let mut service = RouterService {
+        router: router(),
+        error_handler,
+    };
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/other"),
+    );
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(response.headers()[ALLOW], "DELETE, OPTIONS");
+    assert_eq!(body_of(response), "custom");
+}
+
+#[test]
+fn test_allowed_methods() {
+    let router = router();
+
+    assert_eq!(
+        router.allowed_methods("/hello"),
+        vec![
+            Method::GET,
+            Method::HEAD,
+            Method::POST,
+            Method::PUT,
+            Method::OPTIONS
+        ]
+    );
+    assert_eq!(
+        router.allowed_methods("/hi"),
+        vec![Method::GET, Method::HEAD, Method::OPTIONS]
+    );
+    assert!(router.allowed_methods("/missing").is_empty());
+}
+
+#[test]
+fn test_default_method_not_supported_handler() {
+    let router = router();
+    let patch_hello = request(Method::PATCH, "http://www.example.com/hello");
+
+    let handler = router.find_handler_with_defaults(&patch_hello);
+    let response = handler.handle(patch_hello).wait().unwrap();
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(response.headers()[ALLOW], "GET, HEAD, POST, PUT, OPTIONS");
+}
@@ -0,0 +1,218 @@
+extern crate futures;
+extern crate hyper;
+extern crate hyper_router;
+
+mod common;
+
+use common::{body_of, call, request};
+use futures::Future;
+use hyper::header::HeaderValue;
+use hyper::{Body, Method, Request, Response, StatusCode};
+use hyper_router::*;
+use std::sync::{Arc, Mutex};
+
+fn handle_hello(_: Request<Body>) -> Response<Body> {
+    Response::new(Body::from("hello"))
+}
+
+fn recording(log: &Arc<Mutex<Vec<String>>>, name: &'static str) -> impl Middleware {
+    let log = log.clone();
+    move |request: Request<Body>, next: Next| -> ResponseFuture {
+        log.lock().unwrap().push(format!("{} before", name));
+        let log = log.clone();
+        Box::new(next.run(request).map(move |response| {
+            log.lock().unwrap().push(format!("{} after", name));
+            response
+        }))
+    }
+}
+
+#[test]
+fn test_middleware_order() {
+    let log = Arc::new(Mutex::new(vec![]));
+    let router = RouterBuilder::new()
+        .middleware(recording(&log, "first"))
+        .middleware(recording(&log, "second"))
+        .add(Route::get("/hello").using(handle_hello))
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/hello"),
+    );
+    assert_eq!(body_of(response), "hello");
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![
+            "first before",
+            "second before",
+            "second after",
+            "first after"
+        ]
+    );
+}
+
+#[test]
+fn test_middleware_modifies_request_and_response() {
+    fn handle_echo_header(request: Request<Body>) -> Response<Body> {
+        let value = request.headers()["x-user"].to_str().unwrap().to_string();
+        Response::new(Body::from(value))
+    }
+    fn add_user(mut request: Request<Body>, next: Next) -> ResponseFuture {
+        request
+            .headers_mut()
+            .insert("x-user", HeaderValue::from_static("bob"));
+        Box::new(next.run(request).map(|mut response| {
+            response
+                .headers_mut()
+                .insert("x-served-by", HeaderValue::from_static("middleware"));
+            response
+        }))
+    }
+
+    let router = RouterBuilder::new()
+        .middleware(add_user)
+        .add(Route::get("/user").using(handle_echo_header))
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/user"),
+    );
+    assert_eq!(response.headers()["x-served-by"], "middleware");
+    assert_eq!(body_of(response), "bob");
+}
+
+#[test]
+fn test_middleware_short_circuit() {
+    fn deny(_: Request<Body>, _: Next) -> Response<Body> {
+        Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(Body::from("denied"))
+            .unwrap()
+    }
+
+    let log = Arc::new(Mutex::new(vec![]));
+    let router = RouterBuilder::new()
+        .middleware(deny)
+        .middleware(recording(&log, "inner"))
+        .add(Route::get("/hello").using(handle_hello))
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/hello"),
+    );
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(body_of(response), "denied");
+    assert!(log.lock().unwrap().is_empty());
+}
+
+#[test]
+fn test_middleware_wraps_routing_errors() {
+    let log = Arc::new(Mutex::new(vec![]));
+    let router = RouterBuilder::new()
+        .middleware(recording(&log, "logger"))
+        .add(Route::get("/hello").using(handle_hello))
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/missing"),
+    );
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(*log.lock().unwrap(), vec!["logger before", "logger after"]);
+}
+
+#[test]
+fn test_route_middleware() {
+    let log = Arc::new(Mutex::new(vec![]));
+    let router = RouterBuilder::new()
+        .middleware(recording(&log, "router"))
+        .add(
+            Route::get("/hello")
+                .middleware(recording(&log, "first route"))
+                .middleware(recording(&log, "second route"))
+                .using(handle_hello),
+        )
+        .add(Route::get("/other").using(handle_hello))
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/hello"),
+    );
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![
+            "router before",
+            "first route before",
+            "second route before",
+            "second route after",
+            "first route after",
+            "router after"
+        ]
+    );
+
+    log.lock().unwrap().clear();
+    call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/other"),
+    );
+    assert_eq!(*log.lock().unwrap(), vec!["router before", "router after"]);
+}
+
+#[test]
+fn test_prefix_middleware() {
+    fn require_auth(request: Request<Body>, next: Next) -> ResponseFuture {
+        if request.headers().contains_key("authorization") {
+            next.run(request)
+        } else {
+            let mut response = Response::new(Body::empty());
+            *response.status_mut() = StatusCode::UNAUTHORIZED;
+            Box::new(futures::future::ok(response))
+        }
+    }
+
+    let router = RouterBuilder::new()
+        .middleware_at("/admin", require_auth)
+        .add(Route::get("/admin").using(handle_hello))
+        .add(Route::get("/admin/users").using(handle_hello))
+        .add(Route::get("/administrator").using(handle_hello))
+        .add(Route::get("/health").using(handle_hello))
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    let status_of = |service: &mut RouterService, path: &str| {
+        let uri = format!("http://www.example.com{}", path);
+        call(service, request(Method::GET, &uri)).status()
+    };
+    assert_eq!(status_of(&mut service, "/admin"), StatusCode::UNAUTHORIZED);
+    assert_eq!(
+        status_of(&mut service, "/admin/users"),
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        status_of(&mut service, "/admin/missing"),
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(status_of(&mut service, "/administrator"), StatusCode::OK);
+    assert_eq!(status_of(&mut service, "/health"), StatusCode::OK);
+
+    let mut authorized = request(Method::GET, "http://www.example.com/admin/users");
+    authorized
+        .headers_mut()
+        .insert("authorization", HeaderValue::from_static("secret"));
+    assert_eq!(call(&mut service, authorized).status(), StatusCode::OK);
+}
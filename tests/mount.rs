@@ -0,0 +1,167 @@
+extern crate futures;
+extern crate hyper;
+extern crate hyper_router;
+
+mod common;
+
+use common::{body_of, call, request};
+use futures::Future;
+use hyper::header::HeaderValue;
+use hyper::{Body, Method, Request, Response, StatusCode};
+use hyper_router::*;
+
+fn describe_request(request: Request<Body>) -> Response<Body> {
+    let original = request.extensions().get::<OriginalUri>().unwrap();
+    let params = request.extensions().get::<Params>().unwrap();
+    let params: Vec<String> = params
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect();
+    Response::new(Body::from(format!(
+        "{} {} {}",
+        request.uri(),
+        original.0,
+        params.join(",")
+    )))
+}
+
+fn users_router() -> Router {
+    RouterBuilder::new()
+        .add(Route::get("/").using(describe_request))
+        .add(Route::get("/:id").using(describe_request))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_mounted_router_sees_stripped_path() {
+    let router = RouterBuilder::new()
+        .mount("/tenants/:tenant/users", users_router())
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    let response = call(
+        &mut service,
+        request(
+            Method::GET,
+            "http://www.example.com/tenants/acme/users/7?full=1",
+        ),
+    );
+    assert_eq!(
+        body_of(response),
+        "http://www.example.com/7?full=1 \
+         http://www.example.com/tenants/acme/users/7?full=1 tenant=acme,id=7"
+    );
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/tenants/acme/users"),
+    );
+    assert_eq!(
+        body_of(response),
+        "http://www.example.com/ http://www.example.com/tenants/acme/users tenant=acme"
+    );
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/tenants/acme/usersx"),
+    );
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn test_nested_mounts() {
+    let api = RouterBuilder::new()
+        .mount("/users", users_router())
+        .build()
+        .unwrap();
+    let router = RouterBuilder::new().mount("/api", api).build().unwrap();
+    let mut service = RouterService::new(router);
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/api/users/3"),
+    );
+    assert_eq!(
+        body_of(response),
+        "http://www.example.com/3 http://www.example.com/api/users/3 id=3"
+    );
+}
+
+#[test]
+fn test_own_routes_take_priority() {
+    fn handle_me(_: Request<Body>) -> Response<Body> {
+        Response::new(Body::from("me"))
+    }
+
+    let router = RouterBuilder::new()
+        .mount("/users", users_router())
+        .add(Route::get("/users/me").using(handle_me))
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/users/me"),
+    );
+    assert_eq!(body_of(response), "me");
+    assert_eq!(service.router.find_matching_routes("/users/me").len(), 2);
+}
+
+#[test]
+fn test_mounted_router_state_and_middleware() {
+    fn handle_state(request: Request<Body>) -> Response<Body> {
+        let name = request.extensions().get::<State<&'static str>>().unwrap();
+        Response::new(Body::from(**name))
+    }
+    fn tag(request: Request<Body>, next: Next) -> ResponseFuture {
+        let path = request.uri().path().to_string();
+        Box::new(next.run(request).map(move |mut response| {
+            response
+                .headers_mut()
+                .insert("x-seen-path", HeaderValue::from_str(&path).unwrap());
+            response
+        }))
+    }
+
+    let billing = RouterBuilder::new()
+        .with_state("billing")
+        .middleware(tag)
+        .add(Route::get("/invoices").using(handle_state))
+        .build()
+        .unwrap();
+    let router = RouterBuilder::new()
+        .with_state("main")
+        .mount("/billing", billing)
+        .add(Route::get("/home").using(handle_state))
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/billing/invoices"),
+    );
+    assert_eq!(response.headers()["x-seen-path"], "/invoices");
+    assert_eq!(body_of(response), "billing");
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/home"),
+    );
+    assert!(!response.headers().contains_key("x-seen-path"));
+    assert_eq!(body_of(response), "main");
+}
+
+#[test]
+fn test_mounted_router_method_not_allowed() {
+    let router = RouterBuilder::new()
+        .mount("/users", users_router())
+        .build()
+        .unwrap();
+
+    let result = router.find_route(&request(Method::DELETE, "http://www.example.com/users/1"));
+    assert_eq!(result.unwrap_err(), StatusCode::METHOD_NOT_ALLOWED);
+}
@@ -0,0 +1,175 @@
+extern crate futures;
+extern crate hyper;
+extern crate hyper_router;
+
+mod common;
+
+use common::{body_of, echo_params, request, send};
+use hyper::header::LOCATION;
+use hyper::{Method, StatusCode};
+use hyper_router::*;
+
+fn router(path_normalization: PathNormalization) -> Router {
+    RouterBuilder::new()
+        .path_normalization(path_normalization)
+        .add(Route::get("/café").using(echo_params))
+        .add(Route::get("/files/:name").using(echo_params))
+        .add(Route::get("/users/:id/posts").using(echo_params))
+        .add(Route::get("/static/*path").using(echo_params))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_raw_paths_are_default() {
+    let mut service = RouterService::new(router(PathNormalization::default()));
+
+    let response = send(&mut service, Method::GET, "/files/a%20b");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(body_of(response), "name=a%20b");
+    assert_eq!(
+        send(&mut service, Method::GET, "/caf%C3%A9").status(),
+        StatusCode::NOT_FOUND
+    );
+    assert_eq!(
+        send(&mut service, Method::GET, "/users//42/posts").status(),
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[test]
+fn test_percent_decoding() {
+    let mut service = RouterService::new(router(PathNormalization::Normalize));
+
+    assert_eq!(
+        send(&mut service, Method::GET, "/caf%C3%A9").status(),
+        StatusCode::OK
+    );
+    assert_eq!(
+        send(&mut service, Method::GET, "/%63af%c3%a9").status(),
+        StatusCode::OK
+    );
+    assert_eq!(
+        body_of(send(&mut service, Method::GET, "/files/a%20b")),
+        "name=a b"
+    );
+    assert_eq!(
+        body_of(send(&mut service, Method::GET, "/files/100%25")),
+        "name=100%"
+    );
+    assert_eq!(
+        send(&mut service, Method::GET, "/files/%E9").status(),
+        StatusCode::BAD_REQUEST
+    );
+    assert_eq!(
+        send(&mut service, Method::GET, "/files/%zz").status(),
+        StatusCode::BAD_REQUEST
+    );
+}
+
+#[test]
+fn test_signed_percent_encoding_is_rejected() {
+    let mut service = RouterService::new(router(PathNormalization::Normalize));
+
+    for path in &["/files/a%+Fb", "/files/%-1", "/files/%+1a"] {
+        assert_eq!(
+            send(&mut service, Method::GET, path).status(),
+            StatusCode::BAD_REQUEST
+        );
+    }
+    let router = RouterBuilder::new()
+        .path_normalization(PathNormalization::Reject)
+        .build()
+        .unwrap();
+    assert_eq!(
+        router.normalized_path("/%+1a").unwrap_err(),
+        StatusCode::BAD_REQUEST
+    );
+}
+
+#[test]
+fn test_encoded_slash_stays_in_segment() {
+    let mut service = RouterService::new(router(PathNormalization::Normalize));
+
+    assert_eq!(
+        body_of(send(&mut service, Method::GET, "/files/a%2Fb")),
+        "name=a/b"
+    );
+    assert_eq!(
+        body_of(send(&mut service, Method::GET, "/static/css/a%2Fb.css")),
+        "path=css/a/b.css"
+    );
+    assert_eq!(
+        send(&mut service, Method::GET, "/users/4%2F2/posts").status(),
+        StatusCode::OK
+    );
+}
+
+#[test]
+fn test_normalize_policy() {
+    let mut service = RouterService::new(router(PathNormalization::Normalize));
+
+    assert_eq!(
+        body_of(send(&mut service, Method::GET, "/users//42/posts")),
+        "id=42"
+    );
+    assert_eq!(
+        body_of(send(
+            &mut service,
+            Method::GET,
+            "/users/./42/drafts/../posts"
+        )),
+        "id=42"
+    );
+    assert_eq!(
+        body_of(send(&mut service, Method::GET, "/../files/a")),
+        "name=a"
+    );
+    assert_eq!(
+        send(&mut service, Method::GET, "/files/x/%2E%2E").status(),
+        StatusCode::NOT_FOUND
+    );
+    assert_eq!(
+        send(&mut service, Method::GET, "/users/42/posts/").status(),
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[test]
+fn test_reject_policy() {
+    let mut service = RouterService::new(router(PathNormalization::Reject));
+
+    assert_eq!(
+        body_of(send(&mut service, Method::GET, "/files/a%20b")),
+        "name=a b"
+    );
+    for path in &["/users//42/posts", "/users/./42/posts", "/files/x/../a"] {
+        let response = send(&mut service, Method::GET, path);
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(body_of(response), "Routing error: bad request");
+    }
+
+    let router = router(PathNormalization::Reject);
+    assert_eq!(
+        router
+            .find_handler(&request(Method::GET, "http://www.example.com/a//b"))
+            .err(),
+        Some(StatusCode::BAD_REQUEST)
+    );
+    assert_eq!(router.normalized_path("/caf%C3%A9").unwrap(), "/café");
+}
+
+#[test]
+fn test_redirect_location_is_encoded() {
+    let router = RouterBuilder::new()
+        .path_normalization(PathNormalization::Normalize)
+        .trailing_slash(TrailingSlash::RedirectToCanonical)
+        .add(Route::get("/café").using(echo_params))
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    let response = send(&mut service, Method::GET, "/x/../caf%C3%A9/?a=b");
+    assert_eq!(response.status(), StatusCode::MOVED_PERMANENTLY);
+    assert_eq!(response.headers()[LOCATION], "/caf%C3%A9?a=b");
+}
@@ -0,0 +1,93 @@
+extern crate futures;
+extern crate hyper;
+extern crate hyper_router;
+
+mod common;
+
+use common::{body_of, call, handle_any, request};
+use futures::Future;
+use hyper::header::ALLOW;
+use hyper::{Method, StatusCode};
+use hyper_router::*;
+
+fn router() -> Router {
+    RouterBuilder::new()
+        .add(Route::get("/users").using(handle_any))
+        .add(Route::post("/users").using(handle_any))
+        .add(Route::options("/custom").using(handle_any))
+        .add(Route::delete("/custom").using(handle_any))
+        .mount(
+            "/admin",
+            RouterBuilder::new()
+                .add(Route::patch("/settings").using(handle_any))
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_automatic_options_response() {
+    let mut service = RouterService::new(router());
+
+    let response = call(
+        &mut service,
+        request(Method::OPTIONS, "http://www.example.com/users"),
+    );
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(response.headers()[ALLOW], "GET, HEAD, POST, OPTIONS");
+    assert_eq!(body_of(response), "");
+
+    let response = call(
+        &mut service,
+        request(Method::OPTIONS, "http://www.example.com/admin/settings"),
+    );
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(response.headers()[ALLOW], "PATCH, OPTIONS");
+}
+
+#[test]
+fn test_explicit_options_route_takes_priority() {
+    let mut service = RouterService::new(router());
+
+    let response = call(
+        &mut service,
+        request(Method::OPTIONS, "http://www.example.com/custom"),
+    );
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(body_of(response), "handled");
+}
+
+#[test]
+fn test_options_for_unknown_path() {
+    let mut service = RouterService::new(router());
+
+    let response = call(
+        &mut service,
+        request(Method::OPTIONS, "http://www.example.com/missing"),
+    );
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn test_options_asterisk() {
+    let router = router();
+    let options_all = request(Method::OPTIONS, "*");
+    assert_eq!(options_all.uri().path(), "*");
+
+    let handler = router.find_handler_with_defaults(&options_all);
+    let mut service = RouterService::new(router);
+    let response = call(&mut service, options_all);
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(
+        response.headers()[ALLOW],
+        "GET, HEAD, POST, OPTIONS, DELETE, PATCH"
+    );
+
+    let response = handler
+        .handle(request(Method::OPTIONS, "*"))
+        .wait()
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+}
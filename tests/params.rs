@@ -0,0 +1,173 @@
+extern crate futures;
+extern crate hyper;
+extern crate hyper_router;
+
+mod common;
+
+use common::{body_of, call, request};
+use hyper::{Body, Method, Request, Response};
+use hyper_router::*;
+
+#[test]
+fn test_named_segments() {
+    let path = Path::new("/person/:id/posts/:post_id");
+
+    let params = path.params("/person/12/posts/abc").unwrap();
+    assert_eq!(params.get("id"), Some("12"));
+    assert_eq!(params.get("post_id"), Some("abc"));
+    assert_eq!(params.len(), 2);
+
+    assert!(path.params("/person/12/posts").is_none());
+    assert!(path.params("/person/12/13/posts/abc").is_none());
+}
+
+#[test]
+fn test_named_regex_groups() {
+    let path = Path::new(r"/person/(?P<id>\d+)/[a-z]+");
+
+    let params = path.params("/person/42/name").unwrap();
+    assert_eq!(params.get("id"), Some("42"));
+    assert!(path.params("/person/abc/name").is_none());
+}
+
+#[test]
+fn test_find_route_returns_params() {
+    fn handle_person(_: Request<Body>) -> Response<Body> {
+        unimplemented!()
+    }
+
+    let router = RouterBuilder::new()
+        .add(Route::get("/person/:id").using(handle_person))
+        .build()
+        .unwrap();
+
+    let route_match = router
+        .find_route(&request(Method::GET, "http://www.example.com/person/7"))
+        .unwrap();
+    assert_eq!(route_match.params.get("id"), Some("7"));
+}
+
+#[test]
+fn test_params_are_passed_to_handler() {
+    fn handle_post(request: Request<Body>) -> Response<Body> {
+        let params = request.extensions().get::<Params>().unwrap();
+        let body = format!(
+            "{}:{}",
+            params.get("id").unwrap(),
+            params.get("post_id").unwrap()
+        );
+        Response::new(Body::from(body))
+    }
+
+    let router = RouterBuilder::new()
+        .add(Route::get("/person/:id/posts/:post_id").using(handle_post))
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/person/1/posts/2"),
+    );
+    assert_eq!(body_of(response), "1:2");
+}
+
+#[test]
+fn test_typed_params() {
+    let path = Path::new("/user/{id:u64}/{name}");
+
+    let params = path.params("/user/42/bob").unwrap();
+    assert_eq!(params.get("name"), Some("bob"));
+    assert_eq!(params.parse::<u64>("id"), Ok(42));
+
+    assert!(!path.is_match("/user/abc/bob"));
+    assert!(!path.is_match("/user/-1/bob"));
+    assert!(!path.is_match("/user/99999999999999999999/bob"));
+}
+
+#[test]
+fn test_typed_params_select_route() {
+    fn handle_by_id(_: Request<Body>) -> Response<Body> {
+        Response::new(Body::from("by id"))
+    }
+    fn handle_by_uuid(_: Request<Body>) -> Response<Body> {
+        Response::new(Body::from("by uuid"))
+    }
+
+    let router = RouterBuilder::new()
+        .add(Route::get("/item/{id:i32}").using(handle_by_id))
+        .add(Route::get("/item/{id:uuid}").using(handle_by_uuid))
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/item/-5"),
+    );
+    assert_eq!(body_of(response), "by id");
+
+    let response = call(
+        &mut service,
+        request(
+            Method::GET,
+            "http://www.example.com/item/67e55044-10b1-426f-9247-bb680e5fe0c8",
+        ),
+    );
+    assert_eq!(body_of(response), "by uuid");
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/item/abc"),
+    );
+    assert_eq!(response.status(), hyper::StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn test_parse_errors() {
+    let params = Path::new("/user/:id").params("/user/abc").unwrap();
+
+    assert_eq!(
+        params.parse::<u64>("id"),
+        Err(ParamError::Invalid {
+            name: "id".to_string(),
+            value: "abc".to_string()
+        })
+    );
+    assert_eq!(
+        params.parse::<u64>("other"),
+        Err(ParamError::Missing("other".to_string()))
+    );
+
+    fn user_id(params: &Params) -> HttpResult<u64> {
+        Ok(params.parse::<u64>("id")?)
+    }
+    assert_eq!(user_id(&params), Err(hyper::StatusCode::BAD_REQUEST));
+}
+
+#[test]
+fn test_parse_error_in_handler_is_bad_request() {
+    fn show_user(request: Request<Body>) -> HttpResult<Response<Body>> {
+        let params = request.extensions().get::<Params>().unwrap();
+        let id = params.parse::<u64>("id")?;
+        Ok(Response::new(Body::from(format!("user {}", id))))
+    }
+
+    let router = RouterBuilder::new()
+        .add(Route::get("/user/:id").using(show_user))
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/user/42"),
+    );
+    assert_eq!(body_of(response), "user 42");
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/user/abc"),
+    );
+    assert_eq!(response.status(), hyper::StatusCode::BAD_REQUEST);
+}
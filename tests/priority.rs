@@ -0,0 +1,78 @@
+extern crate futures;
+extern crate hyper;
+extern crate hyper_router;
+
+mod common;
+
+use common::{body_of, handle_with, send};
+use hyper::Method;
+use hyper_router::*;
+
+fn router(priority: RoutePriority) -> Router {
+    RouterBuilder::new()
+        .priority(priority)
+        .add(Route::get("/files/.*").using(handle_with("wildcard")))
+        .add(Route::get("/files/:name").using(handle_with("param")))
+        .add(Route::get("/files/{id:u64}").using(handle_with("typed")))
+        .add(Route::get("/files/readme.txt").using(handle_with("static")))
+        .add(Route::get("/files/:name/raw").using(handle_with("raw")))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_insertion_priority_is_default() {
+    let mut service = RouterService::new(router(RoutePriority::default()));
+
+    assert_eq!(
+        body_of(send(&mut service, Method::GET, "/files/readme.txt")),
+        "wildcard"
+    );
+    assert_eq!(
+        body_of(send(&mut service, Method::GET, "/files/42")),
+        "wildcard"
+    );
+    assert_eq!(service.router.conflicts().len(), 3);
+}
+
+#[test]
+fn test_specificity_priority() {
+    let mut service = RouterService::new(router(RoutePriority::Specificity));
+
+    assert_eq!(
+        body_of(send(&mut service, Method::GET, "/files/readme.txt")),
+        "static"
+    );
+    assert_eq!(
+        body_of(send(&mut service, Method::GET, "/files/42")),
+        "typed"
+    );
+    assert_eq!(
+        body_of(send(&mut service, Method::GET, "/files/notes")),
+        "param"
+    );
+    assert_eq!(
+        body_of(send(&mut service, Method::GET, "/files/notes/raw")),
+        "raw"
+    );
+    assert_eq!(
+        body_of(send(&mut service, Method::GET, "/files/notes/old/raw")),
+        "wildcard"
+    );
+    assert!(service.router.conflicts().is_empty());
+}
+
+#[test]
+fn test_equally_specific_routes_keep_insertion_order() {
+    let router = RouterBuilder::new()
+        .priority(RoutePriority::Specificity)
+        .add(Route::get("/user/:name").using(handle_with("name")))
+        .add(Route::get("/user/me").using(handle_with("me")))
+        .add(Route::get("/user/:id").using(handle_with("id")))
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    assert_eq!(body_of(send(&mut service, Method::GET, "/user/me")), "me");
+    assert_eq!(body_of(send(&mut service, Method::GET, "/user/42")), "name");
+}
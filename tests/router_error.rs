@@ -0,0 +1,102 @@
+extern crate futures;
+extern crate hyper;
+extern crate hyper_router;
+
+mod common;
+
+use common::handle_any;
+use hyper::Method;
+use hyper_router::*;
+
+#[test]
+fn test_path_try_new() {
+    assert!(Path::try_new("/user/{id:u64}").is_ok());
+    assert_eq!(
+        Path::try_new("/user/{id:int}").unwrap_err(),
+        PathError::UnknownParamType("{id:int}".to_string())
+    );
+    match Path::try_new("/user/(\\d+") {
+        Err(PathError::InvalidRegex(_)) => {}
+        other => panic!("Expected an invalid regex error, got {:?}", other),
+    }
+}
+
+#[test]
+#[should_panic(expected = "Invalid path `/user/{id:int}`")]
+fn test_path_new_panics_on_invalid_pattern() {
+    Path::new("/user/{id:int}");
+}
+
+#[test]
+fn test_route_try_from() {
+    assert!(Route::try_from(Method::GET, "/user/:id").is_ok());
+    match Route::try_from(Method::POST, "/user/{id:int}") {
+        Err(RouterError::InvalidPath {
+            route,
+            method,
+            pattern,
+            error,
+        }) => {
+            assert_eq!(route, None);
+            assert_eq!(method, Some(Method::POST));
+            assert_eq!(pattern, "/user/{id:int}");
+            assert_eq!(error, PathError::UnknownParamType("{id:int}".to_string()));
+        }
+        _ => panic!("Expected an invalid path error"),
+    }
+}
+
+#[test]
+fn test_build_reports_invalid_route() {
+    let error = RouterBuilder::new()
+        .add(Route::get("/users").using(handle_any))
+        .add(
+            Route::get("/user/{id:int}")
+                .name("user_detail")
+                .using(handle_any),
+        )
+        .build()
+        .unwrap_err();
+
+    assert_eq!(
+        error,
+        RouterError::InvalidPath {
+            route: Some("user_detail".to_string()),
+            method: Some(Method::GET),
+            pattern: "/user/{id:int}".to_string(),
+            error: PathError::UnknownParamType("{id:int}".to_string()),
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "invalid path `/user/{id:int}` of route GET `user_detail`: unknown parameter type in `{id:int}`"
+    );
+}
+
+#[test]
+fn test_build_reports_invalid_prefixes() {
+    let error = RouterBuilder::new()
+        .scope("/api/(v1", |api| {
+            api.add(Route::get("/users").using(handle_any))
+        })
+        .build()
+        .unwrap_err();
+    match error {
+        RouterError::InvalidPath {
+            method, pattern, ..
+        } => {
+            assert_eq!(method, Some(Method::GET));
+            assert_eq!(pattern, "/api/(v1/users");
+        }
+        _ => panic!("Expected an invalid path error"),
+    }
+
+    let error = RouterBuilder::new()
+        .mount("/orgs/{org:name}", RouterBuilder::new().build().unwrap())
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "invalid path `/orgs/{org:name}` of prefix: unknown parameter type in `{org:name}`"
+    );
+}
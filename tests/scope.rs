@@ -0,0 +1,138 @@
+extern crate futures;
+extern crate hyper;
+extern crate hyper_router;
+
+mod common;
+
+use common::{body_of, call, request};
+use hyper::{Body, Method, Request, Response, StatusCode};
+use hyper_router::*;
+
+fn handle_users(_: Request<Body>) -> Response<Body> {
+    Response::new(Body::from("users"))
+}
+
+fn handle_user(request: Request<Body>) -> Response<Body> {
+    let params = request.extensions().get::<Params>().unwrap();
+    Response::new(Body::from(format!("user {}", params.get("id").unwrap())))
+}
+
+fn handle_stats(_: Request<Body>) -> Response<Body> {
+    Response::new(Body::from("stats"))
+}
+
+#[test]
+fn test_scope_prefixes_routes() {
+    let router = RouterBuilder::new()
+        .scope("/api/v1/", |api| {
+            api.add(Route::get("/users").using(handle_users))
+                .add(Route::get("/users/:id").using(handle_user))
+                .scope("/admin", |admin| {
+                    admin.add(Route::get("/stats").using(handle_stats))
+                })
+        })
+        .add(Route::get("/users").using(handle_stats))
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    let body = |service: &mut RouterService, path: &str| {
+        let uri = format!("http://www.example.com{}", path);
+        body_of(call(service, request(Method::GET, &uri)))
+    };
+    assert_eq!(body(&mut service, "/api/v1/users"), "users");
+    assert_eq!(body(&mut service, "/api/v1/users/5"), "user 5");
+    assert_eq!(body(&mut service, "/api/v1/admin/stats"), "stats");
+    assert_eq!(body(&mut service, "/users"), "stats");
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/admin/stats"),
+    );
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn test_scope_prefixes_are_joined_with_slash() {
+    let router = RouterBuilder::new()
+        .scope("/api/", |api| {
+            api.add(Route::get("/").using(handle_users))
+                .scope("v1", |v1| v1.add(Route::get("stats").using(handle_stats)))
+        })
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/api/v1/stats"),
+    );
+    assert_eq!(body_of(response), "stats");
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/apiv1/stats"),
+    );
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/api"),
+    );
+    assert_eq!(body_of(response), "users");
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/api/"),
+    );
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn test_scope_middleware() {
+    fn deny(_: Request<Body>, _: Next) -> Response<Body> {
+        Response::builder()
+            .status(StatusCode::FORBIDDEN)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    let router = RouterBuilder::new()
+        .scope("/api", |api| {
+            api.add(Route::get("/users").using(handle_users))
+                .scope("/admin", |admin| {
+                    admin
+                        .middleware(deny)
+                        .add(Route::get("/stats").using(handle_stats))
+                })
+        })
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/api/users"),
+    );
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/api/admin/stats"),
+    );
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[test]
+fn test_path_with_prefix() {
+    let path = Path::new("/users/{id:u32}").with_prefix("/api/");
+
+    assert_eq!(path.pattern(), "/api/users/{id:u32}");
+    assert!(path.is_match("/api/users/3"));
+    assert!(!path.is_match("/users/3"));
+
+    assert_eq!(
+        Path::new("users").with_prefix("/api").pattern(),
+        "/api/users"
+    );
+    assert_eq!(Path::new("/").with_prefix("/api/").pattern(), "/api");
+    assert_eq!(Path::new("/").with_prefix("/").pattern(), "/");
+}
@@ -0,0 +1,74 @@
+extern crate futures;
+extern crate hyper;
+extern crate hyper_router;
+
+mod common;
+
+use common::{body_of, call, request};
+use hyper::{Body, Method, Request, Response};
+use hyper_router::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct Config {
+    greeting: &'static str,
+}
+
+#[test]
+fn test_state_is_passed_to_handlers() {
+    fn handle_greet(request: Request<Body>) -> Response<Body> {
+        let config = request.extensions().get::<State<Config>>().unwrap();
+        Response::new(Body::from(config.greeting))
+    }
+    fn handle_count(request: Request<Body>) -> Response<Body> {
+        let counter = request.extensions().get::<State<AtomicUsize>>().unwrap();
+        let count = counter.fetch_add(1, Ordering::SeqCst) + 1;
+        Response::new(Body::from(count.to_string()))
+    }
+
+    let router = RouterBuilder::new()
+        .with_state(Config { greeting: "Hello" })
+        .with_state(AtomicUsize::new(0))
+        .add(Route::get("/greet").using(handle_greet))
+        .add(Route::post("/count").using(handle_count))
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/greet"),
+    );
+    assert_eq!(body_of(response), "Hello");
+
+    call(
+        &mut service,
+        request(Method::POST, "http://www.example.com/count"),
+    );
+    let response = call(
+        &mut service,
+        request(Method::POST, "http://www.example.com/count"),
+    );
+    assert_eq!(body_of(response), "2");
+}
+
+#[test]
+fn test_state_added_later_replaces_earlier() {
+    fn handle_greet(request: Request<Body>) -> Response<Body> {
+        let config = request.extensions().get::<State<Config>>().unwrap();
+        Response::new(Body::from(config.greeting))
+    }
+
+    let router = RouterBuilder::new()
+        .with_state(Config { greeting: "Hello" })
+        .with_state(Config { greeting: "Hi" })
+        .add(Route::get("/greet").using(handle_greet))
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    let response = call(
+        &mut service,
+        request(Method::GET, "http://www.example.com/greet"),
+    );
+    assert_eq!(body_of(response), "Hi");
+}
@@ -0,0 +1,158 @@
+extern crate futures;
+extern crate hyper;
+extern crate hyper_router;
+
+mod common;
+
+use common::{body_of, request, send};
+use futures::Future;
+use hyper::header::{ALLOW, LOCATION};
+use hyper::{Body, Method, Request, Response, StatusCode};
+use hyper_router::*;
+
+fn echo_path(request: Request<Body>) -> Response<Body> {
+    Response::new(Body::from(request.uri().path().to_string()))
+}
+
+fn router(trailing_slash: TrailingSlash) -> Router {
+    RouterBuilder::new()
+        .trailing_slash(trailing_slash)
+        .add(Route::get("/users").using(echo_path))
+        .add(Route::post("/users").using(echo_path))
+        .add(Route::get("/posts/").using(echo_path))
+        .add(Route::get("/").using(echo_path))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_strict_policy_is_default() {
+    let mut service = RouterService::new(router(TrailingSlash::default()));
+
+    assert_eq!(
+        send(&mut service, Method::GET, "/users").status(),
+        StatusCode::OK
+    );
+    assert_eq!(
+        send(&mut service, Method::GET, "/users/").status(),
+        StatusCode::NOT_FOUND
+    );
+    assert_eq!(
+        send(&mut service, Method::GET, "/posts").status(),
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[test]
+fn test_ignore_policy() {
+    let mut service = RouterService::new(router(TrailingSlash::Ignore));
+
+    let response = send(&mut service, Method::GET, "/users/");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(body_of(response), "/users/");
+    assert_eq!(
+        send(&mut service, Method::GET, "/posts").status(),
+        StatusCode::OK
+    );
+
+    let response = send(&mut service, Method::DELETE, "/users/");
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(response.headers()[ALLOW], "GET, HEAD, POST, OPTIONS");
+}
+
+#[test]
+fn test_redirect_to_canonical_policy() {
+    let mut service = RouterService::new(router(TrailingSlash::RedirectToCanonical));
+
+    let response = send(&mut service, Method::GET, "/users/?page=2");
+    assert_eq!(response.status(), StatusCode::MOVED_PERMANENTLY);
+    assert_eq!(response.headers()[LOCATION], "/users?page=2");
+
+    let response = send(&mut service, Method::POST, "/users/");
+    assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+    assert_eq!(response.headers()[LOCATION], "/users");
+
+    let response = send(&mut service, Method::GET, "/posts");
+    assert_eq!(response.status(), StatusCode::MOVED_PERMANENTLY);
+    assert_eq!(response.headers()[LOCATION], "/posts/");
+
+    assert_eq!(
+        send(&mut service, Method::GET, "/users").status(),
+        StatusCode::OK
+    );
+    assert_eq!(
+        send(&mut service, Method::GET, "/missing/").status(),
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[test]
+fn test_redirects_keep_method_not_allowed() {
+    for &trailing_slash in &[
+        TrailingSlash::RedirectToCanonical,
+        TrailingSlash::RedirectToSlash,
+    ] {
+        let mut service = RouterService::new(router(trailing_slash));
+
+        let response = send(&mut service, Method::DELETE, "/users/");
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(response.headers()[ALLOW], "GET, HEAD, POST, OPTIONS");
+
+        let response = send(&mut service, Method::OPTIONS, "/users/");
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(response.headers()[ALLOW], "GET, HEAD, POST, OPTIONS");
+
+        let response = send(&mut service, Method::POST, "/posts");
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(response.headers()[ALLOW], "GET, HEAD, OPTIONS");
+    }
+}
+
+#[test]
+fn test_redirect_to_slash_policy() {
+    let mut service = RouterService::new(router(TrailingSlash::RedirectToSlash));
+
+    let response = send(&mut service, Method::GET, "/users");
+    assert_eq!(response.status(), StatusCode::MOVED_PERMANENTLY);
+    assert_eq!(response.headers()[LOCATION], "/users/");
+
+    let response = send(&mut service, Method::GET, "/users/");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(body_of(response), "/users/");
+
+    let response = send(&mut service, Method::GET, "/posts");
+    assert_eq!(response.status(), StatusCode::MOVED_PERMANENTLY);
+    assert_eq!(response.headers()[LOCATION], "/posts/");
+
+    assert_eq!(
+        send(&mut service, Method::GET, "/").status(),
+        StatusCode::OK
+    );
+    assert_eq!(
+        send(&mut service, Method::GET, "/missing").status(),
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[test]
+fn test_find_handler_reports_redirects() {
+    let router = router(TrailingSlash::RedirectToCanonical);
+    let near_miss = request(Method::GET, "http://www.example.com/users/");
+
+    assert_eq!(
+        router.find_handler(&near_miss).err(),
+        Some(StatusCode::MOVED_PERMANENTLY)
+    );
+    assert_eq!(
+        router.trailing_slash_location(near_miss.uri()),
+        Some("/users".to_string())
+    );
+
+    let response = router
+        .find_handler_with_defaults(&near_miss)
+        .handle(near_miss)
+        .wait()
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::MOVED_PERMANENTLY);
+    assert_eq!(response.headers()[LOCATION], "/users");
+}
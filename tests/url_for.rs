@@ -0,0 +1,143 @@
+extern crate futures;
+extern crate hyper;
+extern crate hyper_router;
+
+use hyper::{Body, Request, Response};
+use hyper_router::*;
+
+fn handle_any(_: Request<Body>) -> Response<Body> {
+    Response::new(Body::from("handled"))
+}
+
+fn router() -> Router {
+    RouterBuilder::new()
+        .add(Route::get("/").name("index").using(handle_any))
+        .add(
+            Route::get("/user/:id")
+                .name("user_detail")
+                .using(handle_any),
+        )
+        .add(
+            Route::get("/user/{id:u8}/posts/{slug:slug}")
+                .name("user_post")
+                .using(handle_any),
+        )
+        .add(
+            Route::get(r"/files/(?P<file>\d+)\.txt")
+                .name("file")
+                .using(handle_any),
+        )
+        .add(Route::get("/robots.txt").name("robots").using(handle_any))
+        .add(
+            Route::get(r"/archive/\d+")
+                .name("archive")
+                .using(handle_any),
+        )
+        .scope("/api", |api| {
+            api.add(Route::get("/status").name("status").using(handle_any))
+        })
+        .mount(
+            "/orgs/:org",
+            RouterBuilder::new()
+                .add(Route::get("/").name("org").using(handle_any))
+                .add(Route::get("/members/:id").name("member").using(handle_any))
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_url_for_named_routes() {
+    let router = router();
+
+    assert_eq!(router.url_for("index", &[]).unwrap(), "/");
+    assert_eq!(
+        router.url_for("user_detail", &[("id", "42")]).unwrap(),
+        "/user/42"
+    );
+    assert_eq!(
+        router
+            .url_for("user_post", &[("slug", "hello-world"), ("id", "7")])
+            .unwrap(),
+        "/user/7/posts/hello-world"
+    );
+    assert_eq!(
+        router.url_for("file", &[("file", "12")]).unwrap(),
+        "/files/12.txt"
+    );
+    assert_eq!(router.url_for("robots", &[]).unwrap(), "/robots.txt");
+    assert_eq!(router.url_for("status", &[]).unwrap(), "/api/status");
+}
+
+#[test]
+fn test_url_for_mounted_routes() {
+    let router = router();
+
+    assert_eq!(
+        router.url_for("org", &[("org", "acme")]).unwrap(),
+        "/orgs/acme"
+    );
+    assert_eq!(
+        router
+            .url_for("member", &[("org", "acme"), ("id", "3")])
+            .unwrap(),
+        "/orgs/acme/members/3"
+    );
+    assert_eq!(
+        router.url_for("member", &[("id", "3")]),
+        Err(UrlError::MissingParam("org".to_string()))
+    );
+}
+
+#[test]
+fn test_url_for_encodes_values() {
+    let router = router();
+
+    assert_eq!(
+        router
+            .url_for("user_detail", &[("id", "john doe/2")])
+            .unwrap(),
+        "/user/john%20doe%2F2"
+    );
+}
+
+#[test]
+fn test_url_for_errors() {
+    let router = router();
+
+    assert_eq!(
+        router.url_for("missing", &[]),
+        Err(UrlError::UnknownRoute("missing".to_string()))
+    );
+    assert_eq!(
+        router.url_for("user_detail", &[]),
+        Err(UrlError::MissingParam("id".to_string()))
+    );
+    assert_eq!(
+        router.url_for("user_post", &[("id", "300"), ("slug", "hello")]),
+        Err(UrlError::InvalidParam {
+            name: "id".to_string(),
+            value: "300".to_string()
+        })
+    );
+    assert_eq!(
+        router.url_for("user_post", &[("id", "3"), ("slug", "Hello")]),
+        Err(UrlError::InvalidParam {
+            name: "slug".to_string(),
+            value: "Hello".to_string()
+        })
+    );
+    assert_eq!(
+        router.url_for("file", &[("file", "abc")]),
+        Err(UrlError::InvalidParam {
+            name: "file".to_string(),
+            value: "abc".to_string()
+        })
+    );
+    assert_eq!(
+        router.url_for("archive", &[]),
+        Err(UrlError::NotReversible(r"/archive/\d+".to_string()))
+    );
+}
@@ -0,0 +1,112 @@
+extern crate futures;
+extern crate hyper;
+extern crate hyper_router;
+
+mod common;
+
+use common::{body_of, echo_params, send};
+use hyper::{Body, Method, Request, Response};
+use hyper_router::*;
+
+#[test]
+fn test_named_wildcard_captures_rest_of_path() {
+    let path = Path::new("/static/*file");
+
+    assert_eq!(
+        path.params("/static/css/site.css").unwrap().get("file"),
+        Some("css/site.css")
+    );
+    assert_eq!(path.params("/static/").unwrap().get("file"), Some(""));
+    assert!(!path.is_match("/static"));
+    assert!(!path.is_match("/staticfiles/a"));
+}
+
+#[test]
+fn test_anonymous_wildcard_is_captured_as_tail() {
+    let router = RouterBuilder::new()
+        .add(Route::get("/files/**").using(echo_params))
+        .add(Route::get("/users/:id/*rest").using(echo_params))
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    assert_eq!(
+        body_of(send(&mut service, Method::GET, "/files/a/b/c.txt")),
+        "tail=a/b/c.txt"
+    );
+    assert_eq!(
+        body_of(send(&mut service, Method::GET, "/users/7/posts/1")),
+        "id=7&rest=posts/1"
+    );
+}
+
+#[test]
+fn test_wildcard_must_be_last() {
+    assert_eq!(
+        Path::try_new("/static/*file/raw").unwrap_err(),
+        PathError::WildcardNotLast("*file".to_string())
+    );
+    assert_eq!(
+        Path::try_new("/**/raw").unwrap_err(),
+        PathError::WildcardNotLast("**".to_string())
+    );
+}
+
+#[test]
+fn test_wildcards_have_lowest_specificity() {
+    let router = RouterBuilder::new()
+        .priority(RoutePriority::Specificity)
+        .add(Route::get("/app/**").using(|_: Request<Body>| Response::new(Body::from("app"))))
+        .add(Route::get("/app/:page").using(echo_params))
+        .build()
+        .unwrap();
+    let mut service = RouterService::new(router);
+
+    assert_eq!(
+        body_of(send(&mut service, Method::GET, "/app/settings")),
+        "page=settings"
+    );
+    assert_eq!(
+        body_of(send(&mut service, Method::GET, "/app/settings/profile")),
+        "app"
+    );
+}
+
+#[test]
+fn test_wildcard_shadows_later_routes() {
+    let router = RouterBuilder::new()
+        .add(Route::get("/static/*file").using(echo_params))
+        .add(Route::get("/static/css/:file").using(echo_params))
+        .add(Route::get("/static").using(echo_params))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        router.conflicts(),
+        &[RouterError::ShadowedRoute {
+            route: None,
+            method: Method::GET,
+            pattern: "/static/css/:file".to_string(),
+            shadowed_by: "/static/*file".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_url_for_wildcard() {
+    let router = RouterBuilder::new()
+        .add(
+            Route::get("/static/*file")
+                .name("static")
+                .using(echo_params),
+        )
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        router
+            .url_for("static", &[("file", "css/my site.css")])
+            .unwrap(),
+        "/static/css/my%20site.css"
+    );
+}